@@ -0,0 +1,299 @@
+//! Functions of the Vyper bot factory called through Paloma, and the
+//! conversions used to build their arguments.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use cosmwasm_std::{Binary, Storage, Uint256};
+use ethabi::{Contract, Function, Param, ParamType, StateMutability, Token, Uint};
+
+use crate::address::parse_address;
+use crate::msg::AbiValue;
+use crate::state::ABI;
+use crate::ContractError;
+
+/// Vyper ABI of the bot factory, used until one is stored with `UpdateAbi`.
+pub const DEFAULT_ABI: &str = include_str!("../abi/curve_healthy_bot.json");
+
+// fixed sizes of the `swap_infos` tuple expected by the Curve router
+pub const SWAP_ROUTE_LENGTH: usize = 11;
+pub const SWAP_PARAMS_LENGTH: usize = 5;
+pub const SWAP_POOLS_LENGTH: usize = 5;
+/// Names of the `swap_infos` tuple members, in order.
+pub const SWAP_INFO_FIELDS: [&str; 5] = ["route", "swap_params", "amount", "expected", "pools"];
+/// Padding of short `route` and `pools`.
+pub const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Every remote call of the contract. Adding a call is one entry here.
+fn functions() -> Vec<Function> {
+    use ParamType::Address;
+    use StateMutability::NonPayable;
+    vec![
+        function(
+            "create_next_bot",
+            NonPayable,
+            vec![
+                ("bot_id", uint256()),
+                ("callbacker", Address),
+                ("callback_args", array(uint256())),
+                ("remaining_count", uint256()),
+            ],
+        ),
+        function(
+            "repay_bot",
+            NonPayable,
+            vec![
+                ("bot", array(Address)),
+                ("callbacker", array(Address)),
+                ("callback_args", array(array(uint256()))),
+                ("swap_infos", array(swap_info_type())),
+            ],
+        ),
+        function("set_paloma", NonPayable, vec![]),
+        function("update_compass", NonPayable, vec![("new_compass", Address)]),
+        function(
+            "update_blueprint",
+            NonPayable,
+            vec![("new_blueprint", Address)],
+        ),
+        function(
+            "update_refund_wallet",
+            NonPayable,
+            vec![("new_refund_wallet", Address)],
+        ),
+        function(
+            "update_gas_fee",
+            NonPayable,
+            vec![("new_gas_fee", uint256())],
+        ),
+        function(
+            "update_service_fee_collector",
+            NonPayable,
+            vec![("new_service_fee_collector", Address)],
+        ),
+        function(
+            "update_service_fee",
+            NonPayable,
+            vec![("new_service_fee", uint256())],
+        ),
+    ]
+}
+
+fn function(
+    name: &str,
+    state_mutability: StateMutability,
+    inputs: Vec<(&str, ParamType)>,
+) -> Function {
+    #[allow(deprecated)]
+    Function {
+        name: name.to_string(),
+        inputs: inputs
+            .into_iter()
+            .map(|(name, kind)| Param {
+                name: name.to_string(),
+                kind,
+                internal_type: None,
+            })
+            .collect(),
+        outputs: Vec::new(),
+        constant: None,
+        state_mutability,
+    }
+}
+
+/// The `swap_infos` tuple expected by the Curve router.
+pub fn swap_info_type() -> ParamType {
+    ParamType::Tuple(vec![
+        fixed_array(ParamType::Address, SWAP_ROUTE_LENGTH),
+        fixed_array(
+            fixed_array(uint256(), SWAP_PARAMS_LENGTH),
+            SWAP_PARAMS_LENGTH,
+        ),
+        uint256(),
+        uint256(),
+        fixed_array(ParamType::Address, SWAP_POOLS_LENGTH),
+    ])
+}
+
+fn uint256() -> ParamType {
+    ParamType::Uint(256)
+}
+
+fn array(kind: ParamType) -> ParamType {
+    ParamType::Array(Box::new(kind))
+}
+
+fn fixed_array(kind: ParamType, length: usize) -> ParamType {
+    ParamType::FixedArray(Box::new(kind), length)
+}
+
+/// The contract built from `functions`, once per instance.
+pub fn contract() -> &'static Contract {
+    static CONTRACT: OnceLock<Contract> = OnceLock::new();
+    CONTRACT.get_or_init(|| {
+        let mut functions: BTreeMap<String, Vec<Function>> = BTreeMap::new();
+        for function in self::functions() {
+            functions
+                .entry(function.name.clone())
+                .or_default()
+                .push(function);
+        }
+        Contract {
+            constructor: None,
+            functions,
+            events: BTreeMap::new(),
+            errors: BTreeMap::new(),
+            receive: false,
+            fallback: false,
+        }
+    })
+}
+
+/// `name(inputs)`, ignoring outputs which Paloma never reads.
+pub fn input_signature(function: &Function) -> String {
+    let inputs: Vec<String> = function
+        .inputs
+        .iter()
+        .map(|param| param.kind.to_string())
+        .collect();
+    format!("{}({})", function.name, inputs.join(","))
+}
+
+/// Parses a Vyper ABI JSON and checks that every function the contract calls
+/// is declared in it with the same inputs.
+pub fn validate(abi: &str) -> Result<Contract, ContractError> {
+    let loaded = Contract::load(abi.as_bytes()).map_err(|e| ContractError::InvalidAbi {
+        reason: e.to_string(),
+    })?;
+    for expected in contract().functions() {
+        let actual =
+            loaded
+                .function(&expected.name)
+                .map_err(|_| ContractError::AbiFunctionNotFound {
+                    function: expected.name.clone(),
+                })?;
+        if input_signature(actual) != input_signature(expected) {
+            return Err(ContractError::AbiSignatureMismatch {
+                function: expected.name.clone(),
+                expected: input_signature(expected),
+                actual: input_signature(actual),
+            });
+        }
+    }
+    Ok(loaded)
+}
+
+/// The stored ABI, or `DEFAULT_ABI`.
+pub fn load(storage: &dyn Storage) -> Result<Contract, ContractError> {
+    let abi = ABI.may_load(storage)?;
+    Contract::load(abi.as_deref().unwrap_or(DEFAULT_ABI).as_bytes()).map_err(|e| {
+        ContractError::InvalidAbi {
+            reason: e.to_string(),
+        }
+    })
+}
+
+/// Converts `value` to a token of type `kind`. `path` names the argument in
+/// errors, e.g. `args[1][0]`.
+pub fn value_token(
+    path: &str,
+    value: AbiValue,
+    kind: &ParamType,
+    enforce_checksum: bool,
+) -> Result<Token, ContractError> {
+    let mismatch = || ContractError::AbiValueMismatch {
+        path: path.to_string(),
+        expected: kind.to_string(),
+    };
+    let elements = |values: Vec<AbiValue>, kinds: &mut dyn Iterator<Item = &ParamType>| {
+        values
+            .into_iter()
+            .zip(kinds)
+            .enumerate()
+            .map(|(index, (value, kind))| {
+                value_token(&format!("{path}[{index}]"), value, kind, enforce_checksum)
+            })
+            .collect::<Result<Vec<Token>, ContractError>>()
+    };
+    match (value, kind) {
+        (AbiValue::Address(address), ParamType::Address) => {
+            address_token(path, 0, address.as_str(), enforce_checksum)
+        }
+        (AbiValue::Uint(value), ParamType::Uint(bits)) => {
+            if *bits < 256 && value >= Uint256::one() << (*bits as u32) {
+                return Err(mismatch());
+            }
+            Ok(uint_token(value))
+        }
+        (AbiValue::Bool(value), ParamType::Bool) => Ok(Token::Bool(value)),
+        (AbiValue::Bytes(bytes), ParamType::Bytes) => Ok(Token::Bytes(bytes.to_vec())),
+        (AbiValue::Bytes(bytes), ParamType::FixedBytes(length)) if bytes.len() == *length => {
+            Ok(Token::FixedBytes(bytes.to_vec()))
+        }
+        (AbiValue::Array(values), ParamType::Array(inner)) => Ok(Token::Array(elements(
+            values,
+            &mut std::iter::repeat(inner.as_ref()),
+        )?)),
+        (AbiValue::Array(values), ParamType::FixedArray(inner, length))
+            if values.len() == *length =>
+        {
+            Ok(Token::FixedArray(elements(
+                values,
+                &mut std::iter::repeat(inner.as_ref()),
+            )?))
+        }
+        (AbiValue::Tuple(values), ParamType::Tuple(kinds)) if values.len() == kinds.len() => {
+            Ok(Token::Tuple(elements(values, &mut kinds.iter())?))
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+pub fn function_by_name(function: &str) -> Result<&'static Function, ContractError> {
+    contract()
+        .function(function)
+        .map_err(|_| ContractError::AbiFunctionNotFound {
+            function: function.to_string(),
+        })
+}
+
+/// The function whose 4-byte selector is `selector`.
+pub fn function_by_selector(selector: &[u8]) -> Option<&'static Function> {
+    contract()
+        .functions()
+        .find(|function| function.short_signature().as_slice() == selector)
+}
+
+/// ABI encodes a call of `function` with `tokens`.
+pub fn encode(function: &str, tokens: &[Token]) -> Result<Binary, ContractError> {
+    let payload = function_by_name(function)?
+        .encode_input(tokens)
+        .map_err(|e| ContractError::AbiEncoding {
+            function: function.to_string(),
+            reason: e.to_string(),
+        })?;
+    Ok(Binary::new(payload))
+}
+
+pub fn uint_token(value: Uint256) -> Token {
+    Token::Uint(Uint::from_big_endian(&value.to_be_bytes()))
+}
+
+pub fn uint_array_token(values: Vec<Uint256>) -> Token {
+    Token::Array(values.into_iter().map(uint_token).collect())
+}
+
+/// Parses an EVM address argument, see `parse_address`.
+pub fn address_token(
+    field: &str,
+    index: usize,
+    value: &str,
+    enforce_checksum: bool,
+) -> Result<Token, ContractError> {
+    Ok(Token::Address(parse_address(
+        field,
+        index,
+        value,
+        enforce_checksum,
+    )?))
+}
@@ -0,0 +1,50 @@
+use ethabi::Address;
+use sha3::{Digest, Keccak256};
+use std::str::FromStr;
+
+use crate::error::ContractError;
+
+/// Parses an EVM address given with or without `0x` in any letter case.
+/// With `enforce_checksum`, the address must be in its EIP-55 checksum form.
+pub fn parse_address(
+    field: &str,
+    index: usize,
+    value: &str,
+    enforce_checksum: bool,
+) -> Result<Address, ContractError> {
+    let address = Address::from_str(value).map_err(|_| ContractError::InvalidEvmAddress {
+        field: field.to_string(),
+        index,
+        value: value.to_string(),
+    })?;
+    if enforce_checksum && value.strip_prefix("0x").unwrap_or(value) != checksum(&address) {
+        return Err(ContractError::InvalidEvmChecksum {
+            field: field.to_string(),
+            index,
+            value: value.to_string(),
+        });
+    }
+    Ok(address)
+}
+
+/// The canonical form used in storage keys and responses: lowercase with `0x`.
+pub fn canonical_address(address: &Address) -> String {
+    format!("{:#x}", address)
+}
+
+/// The EIP-55 checksum form of `address` without the `0x` prefix.
+pub fn checksum(address: &Address) -> String {
+    let lower = format!("{:x}", address);
+    let hash = Keccak256::digest(lower.as_bytes());
+    lower
+        .char_indices()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
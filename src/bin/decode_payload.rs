@@ -0,0 +1,165 @@
+//! Decodes a Paloma `payload` against the Vyper functions the contract calls.
+//!
+//! ```sh
+//! cargo run --features tools --bin decode_payload -- <hex payload | tx JSON file>
+//! ```
+//!
+//! With no argument, or `-`, the input is read from stdin. A JSON input is
+//! searched for every `payload` field, hex or base64, e.g. a `PalomaMsg` or a
+//! transaction containing one.
+
+use std::io::Read;
+
+use cosmwasm_std::{Binary, HexBinary};
+use ethabi::{ParamType, Token};
+use serde_json::{json, Map, Value};
+
+use curve_healthy_bot_cw::abi::{self, SWAP_INFO_FIELDS};
+use curve_healthy_bot_cw::address::canonical_address;
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("decode_payload: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let input = match std::env::args().nth(1) {
+        None => read_stdin()?,
+        Some(arg) if arg == "-" => read_stdin()?,
+        Some(arg) if std::path::Path::new(&arg).is_file() => {
+            std::fs::read_to_string(&arg).map_err(|e| format!("{arg}: {e}"))?
+        }
+        Some(arg) => arg,
+    };
+    let input = input.trim();
+    let payloads = if input.starts_with('{') || input.starts_with('[') {
+        let value: Value = serde_json::from_str(input).map_err(|e| e.to_string())?;
+        let mut payloads = vec![];
+        find_payloads(&value, &mut payloads);
+        if payloads.is_empty() {
+            return Err("no payload field in the JSON input".to_string());
+        }
+        payloads
+    } else {
+        vec![input.to_string()]
+    };
+    let mut decoded = payloads
+        .iter()
+        .map(|payload| decode(payload))
+        .collect::<Result<Vec<Value>, String>>()?;
+    let output = if decoded.len() == 1 {
+        decoded.remove(0)
+    } else {
+        Value::Array(decoded)
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?
+    );
+    Ok(())
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| e.to_string())?;
+    Ok(input)
+}
+
+/// Collects every string `payload` field of `value`, depth first.
+fn find_payloads(value: &Value, payloads: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                match field {
+                    Value::String(payload) if key == "payload" => payloads.push(payload.clone()),
+                    _ => find_payloads(field, payloads),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                find_payloads(value, payloads);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Decodes a hex or base64 payload. The encoding is the one whose first four
+/// bytes are the selector of a known function.
+fn decode(payload: &str) -> Result<Value, String> {
+    let candidates = [
+        HexBinary::from_hex(payload.strip_prefix("0x").unwrap_or(payload))
+            .ok()
+            .map(|bytes| bytes.to_vec()),
+        Binary::from_base64(payload)
+            .ok()
+            .map(|bytes| bytes.to_vec()),
+    ];
+    let (function, bytes) = candidates
+        .into_iter()
+        .flatten()
+        .find_map(|bytes| {
+            let function = abi::function_by_selector(bytes.get(..4)?)?;
+            Some((function, bytes))
+        })
+        .ok_or_else(|| format!("not a payload of a known function: {payload}"))?;
+    let tokens = function
+        .decode_input(&bytes[4..])
+        .map_err(|e| format!("{}: {e}", function.name))?;
+    let mut args = Map::new();
+    for (param, token) in function.inputs.iter().zip(tokens) {
+        args.insert(param.name.clone(), token_value(token, &param.kind));
+    }
+    Ok(json!({
+        "function": function.name,
+        "signature": abi::input_signature(function),
+        "selector": format!(
+            "0x{}",
+            HexBinary::from(function.short_signature().as_slice()).to_hex()
+        ),
+        "args": args,
+    }))
+}
+
+/// Addresses are lowercase `0x` hex and uints decimal strings, as in the
+/// contract messages. `swap_infos` tuples are objects keyed by member name.
+fn token_value(token: Token, kind: &ParamType) -> Value {
+    match (token, kind) {
+        (Token::Address(address), _) => Value::String(canonical_address(&address)),
+        (Token::Uint(value), _) | (Token::Int(value), _) => Value::String(value.to_string()),
+        (Token::Bool(value), _) => Value::Bool(value),
+        (Token::String(value), _) => Value::String(value),
+        (Token::Bytes(bytes), _) | (Token::FixedBytes(bytes), _) => {
+            Value::String(format!("0x{}", HexBinary::from(bytes).to_hex()))
+        }
+        (Token::Array(tokens), ParamType::Array(inner))
+        | (Token::FixedArray(tokens), ParamType::FixedArray(inner, _)) => Value::Array(
+            tokens
+                .into_iter()
+                .map(|token| token_value(token, inner))
+                .collect(),
+        ),
+        (Token::Tuple(tokens), ParamType::Tuple(kinds)) if *kind == abi::swap_info_type() => {
+            Value::Object(
+                SWAP_INFO_FIELDS
+                    .iter()
+                    .zip(tokens.into_iter().zip(kinds))
+                    .map(|(name, (token, kind))| (name.to_string(), token_value(token, kind)))
+                    .collect(),
+            )
+        }
+        (Token::Tuple(tokens), ParamType::Tuple(kinds)) => Value::Array(
+            tokens
+                .into_iter()
+                .zip(kinds)
+                .map(|(token, kind)| token_value(token, kind))
+                .collect(),
+        ),
+        (token, _) => Value::String(token.to_string()),
+    }
+}
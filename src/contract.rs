@@ -1,15 +1,27 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 
+use crate::abi::{
+    self, address_token, uint_array_token, uint_token, SWAP_PARAMS_LENGTH, SWAP_POOLS_LENGTH,
+    SWAP_ROUTE_LENGTH, ZERO_ADDRESS,
+};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, GetJobIdResponse, InstantiateMsg, Metadata, PalomaMsg, QueryMsg};
-use crate::state::{State, STATE};
-use cosmwasm_std::CosmosMsg;
-use ethabi::{Contract, Function, Param, ParamType, StateMutability, Token, Uint};
-use std::collections::BTreeMap;
-use std::str::FromStr;
+use crate::migrations;
+use crate::msg::{
+    Action, CooldownResponse, ExecuteMsg, GetJobIdResponse, InstantiateMsg, Metadata, MigrateMsg,
+    PalomaMsg, QueryMsg, SwapAddressKind,
+};
+use crate::state::{
+    BackoffPolicy, Cooldown, State, ABI, APPROVED_POOLS, APPROVED_TOKENS, BOT_ATTEMPTS,
+    CREATE_NEXT_BOT_COOLDOWNS, DEFAULT_MAX_PRICE_AGE, EXECUTED_NEXT_BOTS, REPAY_COOLDOWNS,
+    RETRY_DELAYS, STATE,
+};
+use cosmwasm_std::{CosmosMsg, Empty, Storage, Timestamp, Uint256};
+use cw_storage_plus::Map;
+use ethabi::Token;
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:curve-healthy-bot-cw";
@@ -23,14 +35,27 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     let state = State {
-        retry_delay: msg.retry_delay,
-        job_id: msg.job_id.clone(),
-        owner: info.sender.clone(),
+        retry_delay: validate_retry_delay(msg.retry_delay)?,
+        job_id: validate_job_id(msg.job_id.clone())?,
+        owner: Some(info.sender.clone()),
+        enforce_checksum: msg.enforce_checksum.unwrap_or(false),
+        backoff: msg.backoff.unwrap_or(BackoffPolicy::Fixed),
+        max_attempts: validate_max_attempts(msg.max_attempts)?,
+        max_price_age: validate_max_price_age(msg.max_price_age.unwrap_or(DEFAULT_MAX_PRICE_AGE))?,
         metadata: Metadata {
             creator: msg.creator,
-            signers: msg.signers,
+            signers: dedup_signers(msg.signers),
         },
     };
+    match msg.abi {
+        Some(stored_abi) => {
+            abi::validate(&stored_abi)?;
+            ABI.save(deps.storage, &stored_abi)?;
+        }
+        None => {
+            abi::validate(abi::DEFAULT_ABI)?;
+        }
+    }
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     STATE.save(deps.storage, &state)?;
     Ok(Response::new()
@@ -39,6 +64,127 @@ pub fn instantiate(
         .add_attribute("job_id", msg.job_id))
 }
 
+fn validate_job_id(job_id: String) -> Result<String, ContractError> {
+    if job_id.trim().is_empty() {
+        return Err(ContractError::EmptyJobId {});
+    }
+    Ok(job_id)
+}
+
+fn validate_retry_delay(retry_delay: u64) -> Result<u64, ContractError> {
+    if retry_delay == 0 {
+        return Err(ContractError::ZeroRetryDelay {});
+    }
+    Ok(retry_delay)
+}
+
+fn validate_max_attempts(max_attempts: Option<u32>) -> Result<Option<u32>, ContractError> {
+    if max_attempts == Some(0) {
+        return Err(ContractError::ZeroMaxAttempts {});
+    }
+    Ok(max_attempts)
+}
+
+fn validate_max_price_age(max_price_age: u64) -> Result<u64, ContractError> {
+    if max_price_age == 0 {
+        return Err(ContractError::ZeroMaxPriceAge {});
+    }
+    Ok(max_price_age)
+}
+
+fn dedup_signers(signers: Vec<String>) -> Vec<String> {
+    let mut deduped: Vec<String> = vec![];
+    for signer in signers {
+        if !deduped.contains(&signer) {
+            deduped.push(signer);
+        }
+    }
+    deduped
+}
+
+/// A key is eligible again once the block time is past the expiry of its
+/// last submission.
+fn cooldown_status(cooldown: Option<Cooldown>, now: &Timestamp) -> CooldownResponse {
+    match cooldown {
+        Some(cooldown) => CooldownResponse {
+            last_submitted: Some(cooldown.submitted_at),
+            next_eligible: Some(cooldown.expires_at),
+            eligible: cooldown.expires_at.lt(now),
+            confirmed: cooldown.confirmed,
+        },
+        None => CooldownResponse {
+            last_submitted: None,
+            next_eligible: None,
+            eligible: true,
+            confirmed: None,
+        },
+    }
+}
+
+/// Like `cooldown_status`, but a key confirmed as executed is never eligible
+/// again.
+fn create_next_bot_status(
+    storage: &dyn Storage,
+    bot_id: Uint256,
+    remaining_count: Uint256,
+    now: &Timestamp,
+) -> StdResult<CooldownResponse> {
+    let (bot_id, remaining_count) = (bot_id.to_be_bytes(), remaining_count.to_be_bytes());
+    if let Some(executed) = EXECUTED_NEXT_BOTS.may_load(storage, (&bot_id, &remaining_count))? {
+        return Ok(CooldownResponse {
+            last_submitted: Some(executed.submitted_at),
+            next_eligible: None,
+            eligible: false,
+            confirmed: executed.confirmed,
+        });
+    }
+    Ok(cooldown_status(
+        CREATE_NEXT_BOT_COOLDOWNS.may_load(storage, (&bot_id, &remaining_count))?,
+        now,
+    ))
+}
+
+/// The registry of approved `SwapInfo` addresses of `kind`.
+fn approved_swap_addresses(kind: &SwapAddressKind) -> Map<[u8; 20], Empty> {
+    match kind {
+        SwapAddressKind::Pool => APPROVED_POOLS,
+        SwapAddressKind::Token => APPROVED_TOKENS,
+    }
+}
+
+/// The retry delay configured for `action`, or the default `retry_delay`.
+fn action_delay(storage: &dyn Storage, state: &State, action: &Action) -> StdResult<u64> {
+    Ok(RETRY_DELAYS
+        .may_load(storage, action.as_str())?
+        .unwrap_or(state.retry_delay))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrateContract {
+            contract: stored.contract,
+        });
+    }
+    let previous_version = Version::parse(&stored.version)?;
+    let new_version = Version::parse(CONTRACT_VERSION)?;
+    if previous_version > new_version {
+        return Err(ContractError::CannotMigrateVersion {
+            previous: stored.version,
+            current: CONTRACT_VERSION.to_string(),
+        });
+    }
+    let (applied, attributes) = migrations::run(deps.storage, &previous_version, &new_version)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_version", stored.version)
+        .add_attribute("new_version", CONTRACT_VERSION)
+        .add_attribute("applied_migrations", applied.join(","))
+        .add_attributes(attributes))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -47,7 +193,14 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response<PalomaMsg>, ContractError> {
     match msg {
-        ExecuteMsg::RepayBot { bot_info } => execute::repay_bot(deps, env, info, bot_info),
+        ExecuteMsg::RepayBot {
+            bot_info,
+            pad_swap_info,
+        } => execute::repay_bot(deps, env, info, bot_info, pad_swap_info.unwrap_or(false)),
+        ExecuteMsg::RepayBotByAddress {
+            bots,
+            pad_swap_info,
+        } => execute::repay_bot_by_address(deps, env, info, bots, pad_swap_info.unwrap_or(false)),
         ExecuteMsg::CreateNextBot {
             bot_id,
             callbacker,
@@ -62,6 +215,73 @@ pub fn execute(
             callback_args,
             remaining_count,
         ),
+        ExecuteMsg::AddCallbacker { callbacker } => execute::add_callbacker(deps, info, callbacker),
+        ExecuteMsg::RemoveCallbacker { callbacker } => {
+            execute::remove_callbacker(deps, info, callbacker)
+        }
+        ExecuteMsg::AddSwapAddresses { kind, addresses } => {
+            execute::update_swap_addresses(deps, info, kind, addresses, true)
+        }
+        ExecuteMsg::RemoveSwapAddresses { kind, addresses } => {
+            execute::update_swap_addresses(deps, info, kind, addresses, false)
+        }
+        ExecuteMsg::SetMaxSlippage {
+            token_in,
+            token_out,
+            max_slippage_bps,
+        } => execute::set_max_slippage(deps, info, token_in, token_out, max_slippage_bps),
+        ExecuteMsg::SetReferencePrice {
+            token_in,
+            token_out,
+            price,
+        } => execute::set_reference_price(deps, env, info, token_in, token_out, price),
+        ExecuteMsg::AddOracle { oracle } => execute::add_oracle(deps, info, oracle),
+        ExecuteMsg::RemoveOracle { oracle } => execute::remove_oracle(deps, info, oracle),
+        ExecuteMsg::AddReporter { reporter } => execute::add_reporter(deps, info, reporter),
+        ExecuteMsg::RemoveReporter { reporter } => execute::remove_reporter(deps, info, reporter),
+        ExecuteMsg::ConfirmExecution {
+            action,
+            keys,
+            success,
+            evm_tx_hash,
+        } => execute::confirm_execution(deps, env, info, action, keys, success, evm_tx_hash),
+        ExecuteMsg::RawCall { function, args } => execute::raw_call(deps, info, function, args),
+        ExecuteMsg::AddRawCallFunction { function } => {
+            execute::add_raw_call_function(deps, info, function)
+        }
+        ExecuteMsg::RemoveRawCallFunction { function } => {
+            execute::remove_raw_call_function(deps, info, function)
+        }
+        ExecuteMsg::UpdateAbi { abi } => execute::update_abi(deps, info, abi),
+        ExecuteMsg::UpdateRetryDelay {
+            action,
+            retry_delay,
+        } => execute::update_retry_delay(deps, info, action, retry_delay),
+        ExecuteMsg::UpdateRetryPolicy {
+            backoff,
+            max_attempts,
+        } => execute::update_retry_policy(deps, info, backoff, max_attempts),
+        ExecuteMsg::ResetBotAttempts { bot } => execute::reset_bot_attempts(deps, info, bot),
+        ExecuteMsg::RegisterBot {
+            bot,
+            owner,
+            callbacker,
+            callback_args,
+            collateral,
+            market,
+        } => execute::register_bot(
+            deps,
+            env,
+            info,
+            bot,
+            owner,
+            callbacker,
+            callback_args,
+            collateral,
+            market,
+        ),
+        ExecuteMsg::DeregisterBot { bot } => execute::deregister_bot(deps, info, bot),
+        ExecuteMsg::PruneCooldowns { limit } => execute::prune_cooldowns(deps, env, info, limit),
         ExecuteMsg::SetPaloma {} => execute::set_paloma(deps, info),
         ExecuteMsg::UpdateCompass { new_compass } => {
             execute::update_compass(deps, info, new_compass)
@@ -81,17 +301,305 @@ pub fn execute(
         ExecuteMsg::UpdateServiceFee { new_service_fee } => {
             execute::update_service_fee(deps, info, new_service_fee)
         }
+        ExecuteMsg::ProposeOwner {
+            new_owner,
+            expires_in,
+        } => execute::propose_owner(deps, env, info, new_owner, expires_in),
+        ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipTransfer {} => execute::cancel_ownership_transfer(deps, info),
+        ExecuteMsg::RenounceOwnership {} => execute::renounce_ownership(deps, info),
+        ExecuteMsg::AddOperator { operator } => execute::add_operator(deps, info, operator),
+        ExecuteMsg::RemoveOperator { operator } => execute::remove_operator(deps, info, operator),
+        ExecuteMsg::UpdateConfig {
+            job_id,
+            retry_delay,
+            creator,
+            signers,
+            enforce_checksum,
+            max_price_age,
+        } => execute::update_config(
+            deps,
+            info,
+            job_id,
+            retry_delay,
+            creator,
+            signers,
+            enforce_checksum,
+            max_price_age,
+        ),
     }
 }
 
 pub mod execute {
     use super::*;
-    use crate::msg::BotInfo;
-    use crate::state::WITHDRAW_TIMESTAMP;
-    use crate::ContractError::{AllPending, Unauthorized};
-    use cosmwasm_std::Uint256;
+    use crate::address::{canonical_address, parse_address};
+    use crate::msg::{
+        AbiValue, BotInfo, CooldownKey, RepayBotResult, SkipReason, SkippedBot, SwapAddressKind,
+        SwapInfo,
+    };
+    use crate::state::{
+        Bot, BotStatus, ExecutionRecord, PendingOwner, ReferencePrice, BOTS, CALLBACKER_ALLOWLIST,
+        CREATE_NEXT_BOT_COOLDOWNS, EXECUTION_HISTORY, EXECUTION_SEQ, MAX_SLIPPAGE, OPERATORS,
+        ORACLES, PENDING_OWNER, QUARANTINED_BOTS, RAW_CALL_ALLOWLIST, REFERENCE_PRICES, REPORTERS,
+    };
+    use crate::ContractError::{
+        AbiEncoding, AbiFunctionNotFound, ActionKeyMismatch, AllPending, BotAlreadyRegistered,
+        BotNotActive, CallbackArgsMismatch, CallbackerMismatch, CallbackerNotAllowed, EmptyBotInfo,
+        EmptyExecutionKeys, InvalidEvmTxHash, InvalidSlippageBps, InvalidSwapInfo,
+        MissingReferencePrice, NoPendingOwner, NoPendingSubmission, OwnershipProposalExpired,
+        RawCallNotAllowed, SlippageExceeded, StaleReferencePrice, SwapAddressNotApproved,
+        Unauthorized, UnknownBot, ZeroReferencePrice, ZeroReferenceQuote,
+    };
+    use cosmwasm_std::{Decimal256, Empty, Event, Order, StdError, Uint256};
+    use cw_storage_plus::{KeyDeserialize, PrefixBound};
     use ethabi::Address;
 
+    const DEFAULT_PRUNE_LIMIT: u32 = 30;
+    const MAX_PRUNE_LIMIT: u32 = 100;
+    /// Expired cooldowns removed on every `RepayBot` and `CreateNextBot`.
+    const OPPORTUNISTIC_PRUNE_LIMIT: usize = 5;
+
+    fn check_callbacker(
+        storage: &dyn Storage,
+        index: usize,
+        callbacker: Address,
+    ) -> Result<Address, ContractError> {
+        if !CALLBACKER_ALLOWLIST.has(storage, callbacker.0) {
+            return Err(CallbackerNotAllowed {
+                index,
+                callbacker: canonical_address(&callbacker),
+            });
+        }
+        Ok(callbacker)
+    }
+
+    /// Rejects a non-zero route or pool address that is not approved as
+    /// `kind`.
+    fn check_swap_address(
+        storage: &dyn Storage,
+        field: String,
+        index: usize,
+        address: Address,
+        kind: SwapAddressKind,
+    ) -> Result<Address, ContractError> {
+        if !address.is_zero() && !approved_swap_addresses(&kind).has(storage, address.0) {
+            return Err(SwapAddressNotApproved {
+                field,
+                index,
+                kind: kind.as_str().to_string(),
+                address: canonical_address(&address),
+            });
+        }
+        Ok(address)
+    }
+
+    /// Rejects a swap whose `expected` output is further below the reference
+    /// quote for `amount` than the max slippage of the token pair, or whose
+    /// reference price is older than `max_price_age`. A quote that rounds to
+    /// zero is rejected. Pairs without a max slippage are not checked.
+    fn check_slippage(
+        storage: &dyn Storage,
+        state: &State,
+        now: &Timestamp,
+        index: usize,
+        token_in: Address,
+        token_out: Address,
+        swap_info: &SwapInfo,
+    ) -> Result<(), ContractError> {
+        let pair = (token_in.as_bytes(), token_out.as_bytes());
+        let Some(max_slippage_bps) = MAX_SLIPPAGE.may_load(storage, pair)? else {
+            return Ok(());
+        };
+        let Some(reference) = REFERENCE_PRICES.may_load(storage, pair)? else {
+            return Err(MissingReferencePrice {
+                index,
+                token_in: canonical_address(&token_in),
+                token_out: canonical_address(&token_out),
+            });
+        };
+        if reference.updated_at.plus_seconds(state.max_price_age) < *now {
+            return Err(StaleReferencePrice {
+                index,
+                token_in: canonical_address(&token_in),
+                token_out: canonical_address(&token_out),
+                max_price_age: state.max_price_age,
+            });
+        }
+        let quote = swap_info
+            .amount
+            .checked_mul_floor(reference.price)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        if quote.is_zero() {
+            return Err(ZeroReferenceQuote { index });
+        }
+        if swap_info.expected >= quote {
+            return Ok(());
+        }
+        let slippage_bps = (quote - swap_info.expected).multiply_ratio(10_000u32, quote);
+        if slippage_bps > Uint256::from(max_slippage_bps) {
+            return Err(SlippageExceeded {
+                index,
+                slippage_bps,
+                max_slippage_bps,
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes up to `limit` expired cooldowns, earliest expiry first. Only
+    /// expired entries are visited, through the expiry index.
+    fn prune_expired_cooldowns(
+        storage: &mut dyn Storage,
+        now: &Timestamp,
+        limit: usize,
+    ) -> StdResult<usize> {
+        let repay_keys = REPAY_COOLDOWNS
+            .idx
+            .expires
+            .prefix_range_raw(
+                storage,
+                None,
+                Some(PrefixBound::exclusive(now.nanos())),
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(key, _)| key))
+            .collect::<StdResult<Vec<Vec<u8>>>>()?;
+        let create_next_bot_keys = CREATE_NEXT_BOT_COOLDOWNS
+            .idx
+            .expires
+            .prefix_range_raw(
+                storage,
+                None,
+                Some(PrefixBound::exclusive(now.nanos())),
+                Order::Ascending,
+            )
+            .take(limit - repay_keys.len())
+            .map(|item| item.map(|(key, _)| key))
+            .collect::<StdResult<Vec<Vec<u8>>>>()?;
+        let pruned = repay_keys.len() + create_next_bot_keys.len();
+        for key in repay_keys {
+            REPAY_COOLDOWNS.remove(storage, <[u8; 20]>::from_vec(key)?)?;
+        }
+        for key in create_next_bot_keys {
+            let (bot_id, remaining_count) = <(Vec<u8>, Vec<u8>)>::from_vec(key)?;
+            CREATE_NEXT_BOT_COOLDOWNS.remove(storage, (&bot_id, &remaining_count))?;
+        }
+        Ok(pruned)
+    }
+
+    fn check_length(
+        index: usize,
+        field: &str,
+        expected: usize,
+        actual: usize,
+        pad: bool,
+    ) -> Result<(), ContractError> {
+        if actual == expected || (pad && actual < expected) {
+            Ok(())
+        } else {
+            Err(InvalidSwapInfo {
+                field: field.to_string(),
+                index,
+                expected,
+                actual,
+            })
+        }
+    }
+
+    /// Checks `swap_info` against the fixed-size `swap_infos` ABI tuple.
+    /// With `pad`, short `route`/`pools` are filled with the zero address and
+    /// short `swap_params` with zeros, as the Curve router expects.
+    fn check_swap_info(
+        index: usize,
+        mut swap_info: SwapInfo,
+        pad: bool,
+    ) -> Result<SwapInfo, ContractError> {
+        check_length(
+            index,
+            "swap_info.route",
+            SWAP_ROUTE_LENGTH,
+            swap_info.route.len(),
+            pad,
+        )?;
+        check_length(
+            index,
+            "swap_info.swap_params",
+            SWAP_PARAMS_LENGTH,
+            swap_info.swap_params.len(),
+            pad,
+        )?;
+        for (params_index, swap_params) in swap_info.swap_params.iter().enumerate() {
+            check_length(
+                index,
+                &format!("swap_info.swap_params[{}]", params_index),
+                SWAP_PARAMS_LENGTH,
+                swap_params.len(),
+                pad,
+            )?;
+        }
+        check_length(
+            index,
+            "swap_info.pools",
+            SWAP_POOLS_LENGTH,
+            swap_info.pools.len(),
+            pad,
+        )?;
+        if pad {
+            swap_info
+                .route
+                .resize(SWAP_ROUTE_LENGTH, ZERO_ADDRESS.to_string());
+            swap_info.swap_params.resize(
+                SWAP_PARAMS_LENGTH,
+                vec![Uint256::zero(); SWAP_PARAMS_LENGTH],
+            );
+            for swap_params in swap_info.swap_params.iter_mut() {
+                swap_params.resize(SWAP_PARAMS_LENGTH, Uint256::zero());
+            }
+            swap_info
+                .pools
+                .resize(SWAP_POOLS_LENGTH, ZERO_ADDRESS.to_string());
+        }
+        Ok(swap_info)
+    }
+
+    /// A `CreateNextBot` call as it would be submitted at the current block.
+    pub struct CreateNextBotPlan {
+        pub cooldown: CooldownResponse,
+        /// `None` while the key is in cooldown.
+        pub payload: Option<Binary>,
+    }
+
+    /// Validates and encodes a `CreateNextBot` call without writing to
+    /// storage. Shared by the execute handler and `SimulateCreateNextBot`.
+    pub fn plan_create_next_bot(
+        storage: &dyn Storage,
+        state: &State,
+        now: &Timestamp,
+        bot_id: Uint256,
+        callbacker: String,
+        callback_args: Vec<Uint256>,
+        remaining_count: Uint256,
+    ) -> Result<CreateNextBotPlan, ContractError> {
+        let tokens = vec![
+            uint_token(bot_id),
+            Token::Address(check_callbacker(
+                storage,
+                0,
+                parse_address("callbacker", 0, callbacker.as_str(), state.enforce_checksum)?,
+            )?),
+            uint_array_token(callback_args),
+            uint_token(remaining_count),
+        ];
+        let cooldown = create_next_bot_status(storage, bot_id, remaining_count, now)?;
+        let payload = if cooldown.eligible {
+            Some(abi::encode("create_next_bot", tokens.as_slice())?)
+        } else {
+            None
+        };
+        Ok(CreateNextBotPlan { cooldown, payload })
+    }
+
     pub fn create_next_bot(
         deps: DepsMut,
         env: Env,
@@ -102,286 +610,218 @@ pub mod execute {
         remaining_count: Uint256,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
+        if state.owner.as_ref() != Some(&info.sender) && !OPERATORS.has(deps.storage, &info.sender)
+        {
             return Err(Unauthorized {});
         }
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "create_next_bot".to_string(),
-                vec![Function {
-                    name: "create_next_bot".to_string(),
-                    inputs: vec![
-                        Param {
-                            name: "bot_id".to_string(),
-                            kind: ParamType::Uint(256),
-                            internal_type: None,
-                        },
-                        Param {
-                            name: "callbacker".to_string(),
-                            kind: ParamType::Address,
-                            internal_type: None,
-                        },
-                        Param {
-                            name: "callback_args".to_string(),
-                            kind: ParamType::Array(Box::new(ParamType::Uint(256))),
-                            internal_type: None,
-                        },
-                        Param {
-                            name: "remaining_count".to_string(),
-                            kind: ParamType::Uint(256),
-                            internal_type: None,
-                        },
-                    ],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
-        let mut tokens: Vec<Token> = vec![];
-        let retry_delay: u64 = state.retry_delay;
-        if let Some(timestamp) = WITHDRAW_TIMESTAMP.may_load(
+        prune_expired_cooldowns(deps.storage, &env.block.time, OPPORTUNISTIC_PRUNE_LIMIT)?;
+        let payload = plan_create_next_bot(
             deps.storage,
-            (bot_id.to_string(), remaining_count.to_string()),
-        )? {
-            if timestamp.plus_seconds(retry_delay).lt(&env.block.time) {
-                tokens.push(Token::Uint(Uint::from_big_endian(&bot_id.to_be_bytes())));
-                tokens.push(Token::Address(
-                    Address::from_str(callbacker.as_str()).unwrap(),
-                ));
-                let mut tokens_callback_args: Vec<Token> = vec![];
-                for callback_arg in callback_args {
-                    tokens_callback_args.push(Token::Uint(Uint::from_big_endian(
-                        &callback_arg.to_be_bytes(),
-                    )))
-                }
-                tokens.push(Token::Array(tokens_callback_args));
-                tokens.push(Token::Uint(Uint::from_big_endian(
-                    &remaining_count.to_be_bytes(),
-                )));
-                WITHDRAW_TIMESTAMP.save(
-                    deps.storage,
-                    (bot_id.to_string(), remaining_count.to_string()),
-                    &env.block.time,
-                )?;
-            }
-        } else {
-            tokens.push(Token::Uint(Uint::from_big_endian(&bot_id.to_be_bytes())));
-            tokens.push(Token::Address(
-                Address::from_str(callbacker.as_str()).unwrap(),
-            ));
-            let mut tokens_callback_args: Vec<Token> = vec![];
-            for callback_arg in callback_args {
-                tokens_callback_args.push(Token::Uint(Uint::from_big_endian(
-                    &callback_arg.to_be_bytes(),
-                )))
-            }
-            tokens.push(Token::Array(tokens_callback_args));
-            tokens.push(Token::Uint(Uint::from_big_endian(
-                &remaining_count.to_be_bytes(),
-            )));
-            WITHDRAW_TIMESTAMP.save(
-                deps.storage,
-                (bot_id.to_string(), remaining_count.to_string()),
-                &env.block.time,
-            )?;
-        }
-        if tokens.is_empty() {
-            Err(AllPending {})
-        } else {
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg {
-                    job_id: state.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("create_next_bot")
-                            .unwrap()
-                            .encode_input(tokens.as_slice())
-                            .unwrap(),
-                    ),
-                    metadata: state.metadata,
-                }))
-                .add_attribute("action", "create_next_bot"))
-        }
+            &state,
+            &env.block.time,
+            bot_id,
+            callbacker,
+            callback_args,
+            remaining_count,
+        )?
+        .payload
+        .ok_or(AllPending {})?;
+        let retry_delay = action_delay(deps.storage, &state, &Action::CreateNextBot)?;
+        CREATE_NEXT_BOT_COOLDOWNS.save(
+            deps.storage,
+            (&bot_id.to_be_bytes(), &remaining_count.to_be_bytes()),
+            &Cooldown::new(env.block.time, retry_delay),
+        )?;
+        Ok(Response::new()
+            .add_message(CosmosMsg::Custom(PalomaMsg {
+                job_id: state.job_id,
+                payload,
+                metadata: state.metadata,
+            }))
+            .add_attribute("action", "create_next_bot"))
     }
 
-    pub fn repay_bot(
-        deps: DepsMut,
-        env: Env,
-        info: MessageInfo,
+    /// A `RepayBot` call as it would be submitted at the current block.
+    pub struct RepayBotPlan {
+        /// Bots to submit, with their attempts and cooldown once submitted.
+        pub bots: Vec<([u8; 20], u32, Cooldown)>,
+        /// Bots to quarantine, see `plan_repay_bot`.
+        pub quarantined: Vec<[u8; 20]>,
+        pub result: RepayBotResult,
+        /// `None` when every bot is skipped.
+        pub payload: Option<Binary>,
+    }
+
+    /// Validates `bot_info`, decides which bots are submitted and encodes the
+    /// call without writing to storage. Shared by the execute handler and
+    /// `SimulateRepayBot`.
+    ///
+    /// A bot past its cooldown with `max_attempts` submissions and no
+    /// confirmed success since is quarantined instead of submitted.
+    pub fn plan_repay_bot(
+        storage: &dyn Storage,
+        state: &State,
+        now: &Timestamp,
         bot_info: Vec<BotInfo>,
-    ) -> Result<Response<PalomaMsg>, ContractError> {
-        assert!(!bot_info.is_empty(), "empty bot_info");
-        let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
+        pad_swap_info: bool,
+    ) -> Result<RepayBotPlan, ContractError> {
+        if bot_info.is_empty() {
+            return Err(EmptyBotInfo {});
         }
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "repay_bot".to_string(),
-                vec![Function {
-                    name: "repay_bot".to_string(),
-                    inputs: vec![
-                        Param {
-                            name: "bot".to_string(),
-                            kind: ParamType::Array(Box::new(ParamType::Address)),
-                            internal_type: None,
-                        },
-                        Param {
-                            name: "callbacker".to_string(),
-                            kind: ParamType::Array(Box::new(ParamType::Address)),
-                            internal_type: None,
-                        },
-                        Param {
-                            name: "callback_args".to_string(),
-                            kind: ParamType::Array(Box::new(ParamType::Array(Box::new(
-                                ParamType::Uint(256),
-                            )))),
-                            internal_type: None,
-                        },
-                        Param {
-                            name: "swap_infos".to_string(),
-                            kind: ParamType::Array(Box::new(ParamType::Tuple(vec![
-                                ParamType::FixedArray(Box::new(ParamType::Address), 11),
-                                ParamType::FixedArray(
-                                    Box::new(ParamType::FixedArray(
-                                        Box::new(ParamType::Uint(256)),
-                                        5,
-                                    )),
-                                    5,
-                                ),
-                                ParamType::Uint(256),
-                                ParamType::Uint(256),
-                                ParamType::FixedArray(Box::new(ParamType::Address), 5),
-                            ]))),
-                            internal_type: None,
-                        },
-                    ],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
         let mut token_bots: Vec<Token> = vec![];
         let mut token_callbackers: Vec<Token> = vec![];
         let mut token_callback_args: Vec<Token> = vec![];
         let mut token_swap_infos: Vec<Token> = vec![];
-        let retry_delay: u64 = state.retry_delay;
-        for bot in bot_info {
-            if let Some(timestamp) = WITHDRAW_TIMESTAMP
-                .may_load(deps.storage, (bot.bot.to_owned(), "repay".to_string()))?
-            {
-                if timestamp.plus_seconds(retry_delay).lt(&env.block.time) {
-                    token_bots.push(Token::Address(Address::from_str(bot.bot.as_str()).unwrap()));
-                    token_callbackers.push(Token::Address(
-                        Address::from_str(bot.callbacker.as_str()).unwrap(),
-                    ));
-                    let mut callback_args: Vec<Token> = vec![];
-                    for callback_arg in bot.callback_args {
-                        callback_args.push(Token::Uint(Uint::from_big_endian(
-                            &callback_arg.to_be_bytes(),
-                        )))
-                    }
-                    token_callback_args.push(Token::Array(callback_args));
-                    let mut token_swap_info: Vec<Token> = vec![];
-                    let mut token_route: Vec<Token> = vec![];
-                    for route in bot.swap_info.route {
-                        token_route
-                            .push(Token::Address(Address::from_str(route.as_str()).unwrap()));
-                    }
-                    token_swap_info.push(Token::FixedArray(token_route));
-                    let mut token_swap_params: Vec<Token> = vec![];
-                    for swap_params in bot.swap_info.swap_params {
-                        let mut token_inner_swap_params: Vec<Token> = vec![];
-                        for inner_swap_params in swap_params {
-                            token_inner_swap_params.push(Token::Uint(Uint::from_big_endian(
-                                &inner_swap_params.to_be_bytes(),
-                            )))
-                        }
-                        token_swap_params.push(Token::FixedArray(token_inner_swap_params));
-                    }
-                    token_swap_info.push(Token::FixedArray(token_swap_params));
-                    token_swap_info.push(Token::Uint(Uint::from_big_endian(
-                        &bot.swap_info.amount.to_be_bytes(),
-                    )));
-                    token_swap_info.push(Token::Uint(Uint::from_big_endian(
-                        &bot.swap_info.expected.to_be_bytes(),
-                    )));
-                    let mut token_pools: Vec<Token> = vec![];
-                    for pool in bot.swap_info.pools {
-                        token_pools.push(Token::Address(Address::from_str(pool.as_str()).unwrap()));
-                    }
-                    token_swap_info.push(Token::FixedArray(token_pools));
-                    token_swap_infos.push(Token::Tuple(token_swap_info));
-                    WITHDRAW_TIMESTAMP.save(
-                        deps.storage,
-                        (bot.bot.to_owned(), "repay".to_string()),
-                        &env.block.time,
-                    )?;
-                }
-            } else {
-                token_bots.push(Token::Address(Address::from_str(bot.bot.as_str()).unwrap()));
-                token_callbackers.push(Token::Address(
-                    Address::from_str(bot.callbacker.as_str()).unwrap(),
-                ));
-                let mut callback_args: Vec<Token> = vec![];
-                for callback_arg in bot.callback_args {
-                    callback_args.push(Token::Uint(Uint::from_big_endian(
-                        &callback_arg.to_be_bytes(),
-                    )))
+        let retry_delay = action_delay(storage, state, &Action::Repay)?;
+        let mut bots: Vec<([u8; 20], u32, Cooldown)> = vec![];
+        let mut quarantined: Vec<[u8; 20]> = vec![];
+        let mut result = RepayBotResult {
+            submitted: vec![],
+            skipped: vec![],
+        };
+        for (index, bot) in bot_info.into_iter().enumerate() {
+            let bot_address =
+                parse_address("bot", index, bot.bot.as_str(), state.enforce_checksum)?;
+            let bot_key = canonical_address(&bot_address);
+            let registered = match BOTS.may_load(storage, bot_address.0)? {
+                None => {
+                    return Err(UnknownBot {
+                        index,
+                        bot: bot_key,
+                    })
                 }
-                token_callback_args.push(Token::Array(callback_args));
-                let mut token_swap_info: Vec<Token> = vec![];
-                let mut token_route: Vec<Token> = vec![];
-                for route in bot.swap_info.route {
-                    token_route.push(Token::Address(Address::from_str(route.as_str()).unwrap()));
+                Some(registered) if registered.status != BotStatus::Active => {
+                    return Err(BotNotActive {
+                        index,
+                        bot: bot_key,
+                    })
                 }
-                token_swap_info.push(Token::FixedArray(token_route));
-                let mut token_swap_params: Vec<Token> = vec![];
-                for swap_params in bot.swap_info.swap_params {
-                    let mut token_inner_swap_params: Vec<Token> = vec![];
-                    for inner_swap_params in swap_params {
-                        token_inner_swap_params.push(Token::Uint(Uint::from_big_endian(
-                            &inner_swap_params.to_be_bytes(),
-                        )))
+                Some(registered) => registered,
+            };
+            let callbacker_address = check_callbacker(
+                storage,
+                index,
+                parse_address(
+                    "callbacker",
+                    index,
+                    bot.callbacker.as_str(),
+                    state.enforce_checksum,
+                )?,
+            )?;
+            if canonical_address(&callbacker_address) != registered.callbacker {
+                return Err(CallbackerMismatch {
+                    index,
+                    expected: registered.callbacker,
+                    actual: canonical_address(&callbacker_address),
+                });
+            }
+            if bot.callback_args != registered.callback_args {
+                return Err(CallbackArgsMismatch { index });
+            }
+            let swap_info = check_swap_info(index, bot.swap_info, pad_swap_info)?;
+            let mut token_route: Vec<Token> = vec![];
+            let mut route_tokens: Vec<Address> = vec![];
+            for (route_index, route) in swap_info.route.iter().enumerate() {
+                let field = format!("swap_info.route[{}]", route_index);
+                let address = parse_address(&field, index, route.as_str(), state.enforce_checksum)?;
+                let kind = if route_index % 2 == 0 {
+                    if !address.is_zero() {
+                        route_tokens.push(address);
                     }
-                    token_swap_params.push(Token::FixedArray(token_inner_swap_params));
-                }
-                token_swap_info.push(Token::FixedArray(token_swap_params));
-                token_swap_info.push(Token::Uint(Uint::from_big_endian(
-                    &bot.swap_info.amount.to_be_bytes(),
-                )));
-                token_swap_info.push(Token::Uint(Uint::from_big_endian(
-                    &bot.swap_info.expected.to_be_bytes(),
-                )));
-                let mut token_pools: Vec<Token> = vec![];
-                for pool in bot.swap_info.pools {
-                    token_pools.push(Token::Address(Address::from_str(pool.as_str()).unwrap()));
-                }
-                token_swap_info.push(Token::FixedArray(token_pools));
-                token_swap_infos.push(Token::Tuple(token_swap_info));
-                WITHDRAW_TIMESTAMP.save(
-                    deps.storage,
-                    (bot.bot.to_owned(), "repay".to_string()),
-                    &env.block.time,
+                    SwapAddressKind::Token
+                } else {
+                    SwapAddressKind::Pool
+                };
+                token_route.push(Token::Address(check_swap_address(
+                    storage, field, index, address, kind,
+                )?));
+            }
+            let mut token_pools: Vec<Token> = vec![];
+            for (pool_index, pool) in swap_info.pools.iter().enumerate() {
+                let field = format!("swap_info.pools[{}]", pool_index);
+                let address = parse_address(&field, index, pool.as_str(), state.enforce_checksum)?;
+                token_pools.push(Token::Address(check_swap_address(
+                    storage,
+                    field,
+                    index,
+                    address,
+                    SwapAddressKind::Pool,
+                )?));
+            }
+            if let Some((_, _, cooldown)) = bots.iter().find(|(bot, _, _)| *bot == bot_address.0) {
+                // Listed again in the same call: in cooldown from the first
+                // listing.
+                result.skipped.push(SkippedBot {
+                    bot: bot_key,
+                    reason: SkipReason::Cooldown,
+                    next_eligible: Some(cooldown.expires_at),
+                });
+                continue;
+            }
+            if quarantined.contains(&bot_address.0) || QUARANTINED_BOTS.has(storage, bot_address.0)
+            {
+                result.skipped.push(SkippedBot {
+                    bot: bot_key,
+                    reason: SkipReason::Quarantined,
+                    next_eligible: None,
+                });
+                continue;
+            }
+            let cooldown = cooldown_status(REPAY_COOLDOWNS.may_load(storage, bot_address.0)?, now);
+            if !cooldown.eligible {
+                result.skipped.push(SkippedBot {
+                    bot: bot_key,
+                    reason: SkipReason::Cooldown,
+                    next_eligible: cooldown.next_eligible,
+                });
+                continue;
+            }
+            let attempts = BOT_ATTEMPTS
+                .may_load(storage, bot_address.0)?
+                .unwrap_or_default();
+            if state.max_attempts.is_some_and(|max| attempts >= max) {
+                quarantined.push(bot_address.0);
+                result.skipped.push(SkippedBot {
+                    bot: bot_key,
+                    reason: SkipReason::Quarantined,
+                    next_eligible: None,
+                });
+                continue;
+            }
+            // only bots that are submitted need a current price
+            if let (Some(token_in), Some(token_out)) = (route_tokens.first(), route_tokens.last()) {
+                check_slippage(
+                    storage, state, now, index, *token_in, *token_out, &swap_info,
                 )?;
             }
+            result.submitted.push(bot_key.to_owned());
+            token_bots.push(Token::Address(bot_address));
+            token_callbackers.push(Token::Address(callbacker_address));
+            token_callback_args.push(uint_array_token(bot.callback_args));
+            let mut token_swap_info: Vec<Token> = vec![];
+            token_swap_info.push(Token::FixedArray(token_route));
+            let mut token_swap_params: Vec<Token> = vec![];
+            for swap_params in swap_info.swap_params {
+                let mut token_inner_swap_params: Vec<Token> = vec![];
+                for inner_swap_params in swap_params {
+                    token_inner_swap_params.push(uint_token(inner_swap_params))
+                }
+                token_swap_params.push(Token::FixedArray(token_inner_swap_params));
+            }
+            token_swap_info.push(Token::FixedArray(token_swap_params));
+            token_swap_info.push(uint_token(swap_info.amount));
+            token_swap_info.push(uint_token(swap_info.expected));
+            token_swap_info.push(Token::FixedArray(token_pools));
+            token_swap_infos.push(Token::Tuple(token_swap_info));
+            let attempts = attempts.saturating_add(1);
+            bots.push((
+                bot_address.0,
+                attempts,
+                Cooldown::new(*now, state.backoff.delay(retry_delay, attempts)),
+            ));
         }
-        if token_bots.is_empty() {
-            Err(AllPending {})
+        let payload = if bots.is_empty() {
+            None
         } else {
             let tokens = vec![
                 Token::Array(token_bots),
@@ -389,58 +829,219 @@ pub mod execute {
                 Token::Array(token_callback_args),
                 Token::Array(token_swap_infos),
             ];
-            Ok(Response::new()
-                .add_message(CosmosMsg::Custom(PalomaMsg {
-                    job_id: state.job_id,
-                    payload: Binary::new(
-                        contract
-                            .function("repay_bot")
-                            .unwrap()
-                            .encode_input(tokens.as_slice())
-                            .unwrap(),
-                    ),
-                    metadata: state.metadata,
-                }))
-                .add_attribute("action", "repay_bot"))
+            Some(abi::encode("repay_bot", tokens.as_slice())?)
+        };
+        Ok(RepayBotPlan {
+            bots,
+            quarantined,
+            result,
+            payload,
+        })
+    }
+
+    pub fn repay_bot(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        bot_info: Vec<BotInfo>,
+        pad_swap_info: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) && !OPERATORS.has(deps.storage, &info.sender)
+        {
+            return Err(Unauthorized {});
+        }
+        prune_expired_cooldowns(deps.storage, &env.block.time, OPPORTUNISTIC_PRUNE_LIMIT)?;
+        let RepayBotPlan {
+            bots,
+            quarantined,
+            result,
+            payload,
+        } = plan_repay_bot(
+            deps.storage,
+            &state,
+            &env.block.time,
+            bot_info,
+            pad_swap_info,
+        )?;
+        if bots.is_empty() && quarantined.is_empty() {
+            return Err(AllPending {});
+        }
+        for bot in quarantined {
+            QUARANTINED_BOTS.save(deps.storage, bot, &env.block.time)?;
         }
+        for (bot, attempts, cooldown) in bots {
+            REPAY_COOLDOWNS.save(deps.storage, bot, &cooldown)?;
+            BOT_ATTEMPTS.save(deps.storage, bot, &attempts)?;
+        }
+        let skipped_events = result.skipped.iter().map(|skipped| {
+            Event::new("repay_bot_skipped")
+                .add_attribute("bot", skipped.bot.as_str())
+                .add_attribute("reason", skipped.reason.to_string())
+                .add_attribute(
+                    "next_eligible",
+                    skipped
+                        .next_eligible
+                        .map(|time| time.seconds().to_string())
+                        .unwrap_or_default(),
+                )
+        });
+        // only quarantines when every bot is skipped
+        let messages = payload.map(|payload| {
+            CosmosMsg::Custom(PalomaMsg {
+                job_id: state.job_id,
+                payload,
+                metadata: state.metadata,
+            })
+        });
+        Ok(Response::new()
+            .add_messages(messages)
+            .add_events(skipped_events)
+            .add_attribute("action", "repay_bot")
+            .add_attribute("submitted_bots", result.submitted.join(","))
+            .add_attribute(
+                "skipped_bots",
+                result
+                    .skipped
+                    .iter()
+                    .map(|skipped| skipped.bot.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+            .set_data(to_json_binary(&result)?))
     }
 
-    pub fn set_paloma(
+    /// Fills in the callbacker and callback args of each bot from the registry
+    /// and runs `repay_bot`.
+    pub fn repay_bot_by_address(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        bots: Vec<(String, SwapInfo)>,
+        pad_swap_info: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) && !OPERATORS.has(deps.storage, &info.sender)
+        {
+            return Err(Unauthorized {});
+        }
+        let mut bot_info: Vec<BotInfo> = vec![];
+        for (index, (bot, swap_info)) in bots.into_iter().enumerate() {
+            let bot_address = parse_address("bot", index, bot.as_str(), state.enforce_checksum)?;
+            let registered =
+                BOTS.may_load(deps.storage, bot_address.0)?
+                    .ok_or_else(|| UnknownBot {
+                        index,
+                        bot: canonical_address(&bot_address),
+                    })?;
+            bot_info.push(BotInfo {
+                bot,
+                callbacker: registered.callbacker,
+                callback_args: registered.callback_args,
+                swap_info,
+            });
+        }
+        repay_bot(deps, env, info, bot_info, pad_swap_info)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_bot(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        bot: String,
+        owner: String,
+        callbacker: String,
+        callback_args: Vec<Uint256>,
+        collateral: String,
+        market: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let parse = |field: &str, value: String| {
+            parse_address(field, 0, value.as_str(), state.enforce_checksum)
+        };
+        let bot_address = parse("bot", bot)?;
+        let bot_key = canonical_address(&bot_address);
+        if BOTS
+            .may_load(deps.storage, bot_address.0)?
+            .is_some_and(|registered| registered.status == BotStatus::Active)
+        {
+            return Err(BotAlreadyRegistered { bot: bot_key });
+        }
+        let registered = Bot {
+            owner: canonical_address(&parse("owner", owner)?),
+            callbacker: canonical_address(&parse("callbacker", callbacker)?),
+            callback_args,
+            collateral: canonical_address(&parse("collateral", collateral)?),
+            market: canonical_address(&parse("market", market)?),
+            created_at: env.block.time,
+            status: BotStatus::Active,
+        };
+        BOTS.save(deps.storage, bot_address.0, &registered)?;
+        Ok(Response::new()
+            .add_attribute("action", "register_bot")
+            .add_attribute("bot", bot_key)
+            .add_attribute("owner", registered.owner)
+            .add_attribute("callbacker", registered.callbacker))
+    }
+
+    pub fn deregister_bot(
         deps: DepsMut,
         info: MessageInfo,
+        bot: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
+        if state.owner.as_ref() != Some(&info.sender) {
             return Err(Unauthorized {});
         }
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "set_paloma".to_string(),
-                vec![Function {
-                    name: "set_paloma".to_string(),
-                    inputs: vec![],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
+        let bot_address = parse_address("bot", 0, bot.as_str(), state.enforce_checksum)?;
+        let bot_key = canonical_address(&bot_address);
+        let Some(mut registered) = BOTS.may_load(deps.storage, bot_address.0)? else {
+            return Err(UnknownBot {
+                index: 0,
+                bot: bot_key,
+            });
         };
+        registered.status = BotStatus::Deregistered;
+        BOTS.save(deps.storage, bot_address.0, &registered)?;
+        Ok(Response::new()
+            .add_attribute("action", "deregister_bot")
+            .add_attribute("bot", bot_key))
+    }
+
+    pub fn prune_cooldowns(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        limit: Option<u32>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) && !OPERATORS.has(deps.storage, &info.sender)
+        {
+            return Err(Unauthorized {});
+        }
+        let limit = limit.unwrap_or(DEFAULT_PRUNE_LIMIT).min(MAX_PRUNE_LIMIT) as usize;
+        let pruned = prune_expired_cooldowns(deps.storage, &env.block.time, limit)?;
+        Ok(Response::new()
+            .add_attribute("action", "prune_cooldowns")
+            .add_attribute("pruned", pruned.to_string()))
+    }
+
+    pub fn set_paloma(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("set_paloma")
-                        .unwrap()
-                        .encode_input(&[])
-                        .unwrap(),
-                ),
+                payload: abi::encode("set_paloma", &[])?,
                 metadata: state.metadata,
             }))
             .add_attribute("action", "set_paloma"))
@@ -452,43 +1053,19 @@ pub mod execute {
         new_compass: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
-        }
-        let new_compass_address: Address = Address::from_str(new_compass.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_compass".to_string(),
-                vec![Function {
-                    name: "update_compass".to_string(),
-                    inputs: vec![Param {
-                        name: "new_compass".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
-
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let new_compass = address_token(
+            "new_compass",
+            0,
+            new_compass.as_str(),
+            state.enforce_checksum,
+        )?;
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("update_compass")
-                        .unwrap()
-                        .encode_input(&[Token::Address(new_compass_address)])
-                        .unwrap(),
-                ),
+                payload: abi::encode("update_compass", &[new_compass])?,
                 metadata: state.metadata,
             }))
             .add_attribute("action", "update_compass"))
@@ -500,43 +1077,19 @@ pub mod execute {
         new_blueprint: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
-        }
-        let new_blueprint_address: Address = Address::from_str(new_blueprint.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_blueprint".to_string(),
-                vec![Function {
-                    name: "update_blueprint".to_string(),
-                    inputs: vec![Param {
-                        name: "new_compass".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
-
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let new_blueprint = address_token(
+            "new_blueprint",
+            0,
+            new_blueprint.as_str(),
+            state.enforce_checksum,
+        )?;
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("update_blueprint")
-                        .unwrap()
-                        .encode_input(&[Token::Address(new_blueprint_address)])
-                        .unwrap(),
-                ),
+                payload: abi::encode("update_blueprint", &[new_blueprint])?,
                 metadata: state.metadata,
             }))
             .add_attribute("action", "update_blueprint"))
@@ -545,47 +1098,22 @@ pub mod execute {
     pub fn update_refund_wallet(
         deps: DepsMut,
         info: MessageInfo,
-        new_compass: String,
+        new_refund_wallet: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
-        }
-        let update_refund_wallet_address: Address =
-            Address::from_str(new_compass.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_refund_wallet".to_string(),
-                vec![Function {
-                    name: "update_refund_wallet".to_string(),
-                    inputs: vec![Param {
-                        name: "new_compass".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
-
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let new_refund_wallet = address_token(
+            "new_refund_wallet",
+            0,
+            new_refund_wallet.as_str(),
+            state.enforce_checksum,
+        )?;
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("update_refund_wallet")
-                        .unwrap()
-                        .encode_input(&[Token::Address(update_refund_wallet_address)])
-                        .unwrap(),
-                ),
+                payload: abi::encode("update_refund_wallet", &[new_refund_wallet])?,
                 metadata: state.metadata,
             }))
             .add_attribute("action", "update_refund_wallet"))
@@ -597,44 +1125,13 @@ pub mod execute {
         new_gas_fee: Uint256,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
-        }
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_gas_fee".to_string(),
-                vec![Function {
-                    name: "update_gas_fee".to_string(),
-                    inputs: vec![Param {
-                        name: "new_gas_fee".to_string(),
-                        kind: ParamType::Uint(256),
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
-
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("update_gas_fee")
-                        .unwrap()
-                        .encode_input(&[Token::Uint(Uint::from_big_endian(
-                            &new_gas_fee.to_be_bytes(),
-                        ))])
-                        .unwrap(),
-                ),
+                payload: abi::encode("update_gas_fee", &[uint_token(new_gas_fee)])?,
                 metadata: state.metadata,
             }))
             .add_attribute("action", "update_gas_fee"))
@@ -646,44 +1143,19 @@ pub mod execute {
         new_service_fee_collector: String,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
-        }
-        let new_service_fee_collector_address: Address =
-            Address::from_str(new_service_fee_collector.as_str()).unwrap();
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_service_fee_collector".to_string(),
-                vec![Function {
-                    name: "update_service_fee_collector".to_string(),
-                    inputs: vec![Param {
-                        name: "new_service_fee_collector".to_string(),
-                        kind: ParamType::Address,
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
-        };
-
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let new_service_fee_collector = address_token(
+            "new_service_fee_collector",
+            0,
+            new_service_fee_collector.as_str(),
+            state.enforce_checksum,
+        )?;
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("update_service_fee_collector")
-                        .unwrap()
-                        .encode_input(&[Token::Address(new_service_fee_collector_address)])
-                        .unwrap(),
-                ),
+                payload: abi::encode("update_service_fee_collector", &[new_service_fee_collector])?,
                 metadata: state.metadata,
             }))
             .add_attribute("action", "update_service_fee_collector"))
@@ -695,59 +1167,815 @@ pub mod execute {
         new_service_fee: Uint256,
     ) -> Result<Response<PalomaMsg>, ContractError> {
         let state = STATE.load(deps.storage)?;
-        if state.owner != info.sender {
-            return Err(Unauthorized {});
-        }
-        #[allow(deprecated)]
-        let contract: Contract = Contract {
-            constructor: None,
-            functions: BTreeMap::from_iter(vec![(
-                "update_service_fee".to_string(),
-                vec![Function {
-                    name: "update_service_fee".to_string(),
-                    inputs: vec![Param {
-                        name: "new_service_fee".to_string(),
-                        kind: ParamType::Uint(256),
-                        internal_type: None,
-                    }],
-                    outputs: Vec::new(),
-                    constant: None,
-                    state_mutability: StateMutability::NonPayable,
-                }],
-            )]),
-            events: BTreeMap::new(),
-            errors: BTreeMap::new(),
-            receive: false,
-            fallback: false,
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        Ok(Response::new()
+            .add_message(CosmosMsg::Custom(PalomaMsg {
+                job_id: state.job_id,
+                payload: abi::encode("update_service_fee", &[uint_token(new_service_fee)])?,
+                metadata: state.metadata,
+            }))
+            .add_attribute("action", "update_service_fee"))
+    }
+
+    pub fn propose_owner(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+        expires_in: Option<u64>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let pending_owner = PendingOwner {
+            owner: deps.api.addr_validate(new_owner.as_str())?,
+            expires_at: expires_in.map(|seconds| env.block.time.plus_seconds(seconds)),
         };
+        PENDING_OWNER.save(deps.storage, &pending_owner)?;
+        let mut response = Response::new()
+            .add_attribute("action", "propose_owner")
+            .add_attribute("pending_owner", pending_owner.owner);
+        if let Some(expires_at) = pending_owner.expires_at {
+            response = response.add_attribute("expires_at", expires_at.seconds().to_string());
+        }
+        Ok(response)
+    }
+
+    pub fn accept_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let pending_owner = PENDING_OWNER
+            .may_load(deps.storage)?
+            .ok_or(NoPendingOwner {})?;
+        if pending_owner.owner != info.sender {
+            return Err(Unauthorized {});
+        }
+        if let Some(expires_at) = pending_owner.expires_at {
+            if expires_at.le(&env.block.time) {
+                return Err(OwnershipProposalExpired {});
+            }
+        }
+        let mut state = STATE.load(deps.storage)?;
+        let previous_owner = state.owner.replace(info.sender.clone());
+        STATE.save(deps.storage, &state)?;
+        PENDING_OWNER.remove(deps.storage);
+        Ok(Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute(
+                "previous_owner",
+                previous_owner.map(String::from).unwrap_or_default(),
+            )
+            .add_attribute("owner", info.sender))
+    }
+
+    pub fn cancel_ownership_transfer(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        if !PENDING_OWNER.exists(deps.storage) {
+            return Err(NoPendingOwner {});
+        }
+        PENDING_OWNER.remove(deps.storage);
+        Ok(Response::new().add_attribute("action", "cancel_ownership_transfer"))
+    }
+
+    pub fn renounce_ownership(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let mut state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        state.owner = None;
+        STATE.save(deps.storage, &state)?;
+        PENDING_OWNER.remove(deps.storage);
+        Ok(Response::new()
+            .add_attribute("action", "renounce_ownership")
+            .add_attribute("previous_owner", info.sender))
+    }
+
+    pub fn add_operator(
+        deps: DepsMut,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let operator = deps.api.addr_validate(operator.as_str())?;
+        OPERATORS.save(deps.storage, &operator, &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "add_operator")
+            .add_attribute("operator", operator))
+    }
+
+    pub fn remove_operator(
+        deps: DepsMut,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let operator = deps.api.addr_validate(operator.as_str())?;
+        OPERATORS.remove(deps.storage, &operator);
+        Ok(Response::new()
+            .add_attribute("action", "remove_operator")
+            .add_attribute("operator", operator))
+    }
+
+    pub fn add_callbacker(
+        deps: DepsMut,
+        info: MessageInfo,
+        callbacker: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let callbacker =
+            parse_address("callbacker", 0, callbacker.as_str(), state.enforce_checksum)?;
+        CALLBACKER_ALLOWLIST.save(deps.storage, callbacker.0, &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "add_callbacker")
+            .add_attribute("callbacker", canonical_address(&callbacker)))
+    }
+
+    pub fn remove_callbacker(
+        deps: DepsMut,
+        info: MessageInfo,
+        callbacker: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let callbacker =
+            parse_address("callbacker", 0, callbacker.as_str(), state.enforce_checksum)?;
+        CALLBACKER_ALLOWLIST.remove(deps.storage, callbacker.0);
+        Ok(Response::new()
+            .add_attribute("action", "remove_callbacker")
+            .add_attribute("callbacker", canonical_address(&callbacker)))
+    }
+
+    pub fn update_swap_addresses(
+        deps: DepsMut,
+        info: MessageInfo,
+        kind: SwapAddressKind,
+        addresses: Vec<String>,
+        approve: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let approved = approved_swap_addresses(&kind);
+        let mut updated: Vec<String> = vec![];
+        for (index, address) in addresses.iter().enumerate() {
+            let address = parse_address("addresses", index, address, state.enforce_checksum)?;
+            if approve {
+                approved.save(deps.storage, address.0, &Empty {})?;
+            } else {
+                approved.remove(deps.storage, address.0);
+            }
+            updated.push(canonical_address(&address));
+        }
+        Ok(Response::new()
+            .add_attribute(
+                "action",
+                if approve {
+                    "add_swap_addresses"
+                } else {
+                    "remove_swap_addresses"
+                },
+            )
+            .add_attribute("kind", kind.as_str())
+            .add_attribute("addresses", updated.join(",")))
+    }
 
+    pub fn set_max_slippage(
+        deps: DepsMut,
+        info: MessageInfo,
+        token_in: String,
+        token_out: String,
+        max_slippage_bps: Option<u16>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let token_in = parse_address("token_in", 0, token_in.as_str(), state.enforce_checksum)?;
+        let token_out = parse_address("token_out", 0, token_out.as_str(), state.enforce_checksum)?;
+        let pair = (token_in.as_bytes(), token_out.as_bytes());
+        match max_slippage_bps {
+            Some(max_slippage_bps) if max_slippage_bps > 10_000 => {
+                return Err(InvalidSlippageBps {})
+            }
+            Some(max_slippage_bps) => MAX_SLIPPAGE.save(deps.storage, pair, &max_slippage_bps)?,
+            None => MAX_SLIPPAGE.remove(deps.storage, pair),
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_max_slippage")
+            .add_attribute("token_in", canonical_address(&token_in))
+            .add_attribute("token_out", canonical_address(&token_out))
+            .add_attribute(
+                "max_slippage_bps",
+                max_slippage_bps
+                    .map(|max| max.to_string())
+                    .unwrap_or_default(),
+            ))
+    }
+
+    pub fn set_reference_price(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_in: String,
+        token_out: String,
+        price: Decimal256,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) && !ORACLES.has(deps.storage, &info.sender) {
+            return Err(Unauthorized {});
+        }
+        if price.is_zero() {
+            return Err(ZeroReferencePrice {});
+        }
+        let token_in = parse_address("token_in", 0, token_in.as_str(), state.enforce_checksum)?;
+        let token_out = parse_address("token_out", 0, token_out.as_str(), state.enforce_checksum)?;
+        REFERENCE_PRICES.save(
+            deps.storage,
+            (token_in.as_bytes(), token_out.as_bytes()),
+            &ReferencePrice {
+                price,
+                updated_at: env.block.time,
+            },
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "set_reference_price")
+            .add_attribute("token_in", canonical_address(&token_in))
+            .add_attribute("token_out", canonical_address(&token_out))
+            .add_attribute("price", price.to_string()))
+    }
+
+    pub fn add_oracle(
+        deps: DepsMut,
+        info: MessageInfo,
+        oracle: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let oracle = deps.api.addr_validate(oracle.as_str())?;
+        ORACLES.save(deps.storage, &oracle, &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "add_oracle")
+            .add_attribute("oracle", oracle))
+    }
+
+    pub fn remove_oracle(
+        deps: DepsMut,
+        info: MessageInfo,
+        oracle: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let oracle = deps.api.addr_validate(oracle.as_str())?;
+        ORACLES.remove(deps.storage, &oracle);
+        Ok(Response::new()
+            .add_attribute("action", "remove_oracle")
+            .add_attribute("oracle", oracle))
+    }
+
+    pub fn add_reporter(
+        deps: DepsMut,
+        info: MessageInfo,
+        reporter: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let reporter = deps.api.addr_validate(reporter.as_str())?;
+        REPORTERS.save(deps.storage, &reporter, &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "add_reporter")
+            .add_attribute("reporter", reporter))
+    }
+
+    pub fn remove_reporter(
+        deps: DepsMut,
+        info: MessageInfo,
+        reporter: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let reporter = deps.api.addr_validate(reporter.as_str())?;
+        REPORTERS.remove(deps.storage, &reporter);
+        Ok(Response::new()
+            .add_attribute("action", "remove_reporter")
+            .add_attribute("reporter", reporter))
+    }
+
+    /// The cooldown of key `index` with the reported outcome, if its last
+    /// submission awaits confirmation.
+    fn pending_submission(
+        cooldown: Option<Cooldown>,
+        index: usize,
+        success: bool,
+    ) -> Result<Cooldown, ContractError> {
+        match cooldown {
+            Some(cooldown) if cooldown.confirmed.is_none() => Ok(Cooldown {
+                confirmed: Some(success),
+                ..cooldown
+            }),
+            _ => Err(NoPendingSubmission { index }),
+        }
+    }
+
+    fn validate_evm_tx_hash(evm_tx_hash: String) -> Result<String, ContractError> {
+        match evm_tx_hash.strip_prefix("0x") {
+            Some(hash) if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+                Ok(evm_tx_hash.to_lowercase())
+            }
+            _ => Err(InvalidEvmTxHash { value: evm_tx_hash }),
+        }
+    }
+
+    /// Records the outcome of the submission awaiting confirmation of each
+    /// key. A failed execution keeps the cooldown, so the key is retried
+    /// after its backoff delay. A successful `repay` resets the attempt
+    /// counter of the bot and releases it from quarantine; a successful
+    /// `create_next_bot` is never submitted again.
+    pub fn confirm_execution(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: Action,
+        keys: Vec<CooldownKey>,
+        success: bool,
+        evm_tx_hash: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        if keys.is_empty() {
+            return Err(EmptyExecutionKeys {});
+        }
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) && !REPORTERS.has(deps.storage, &info.sender)
+        {
+            return Err(Unauthorized {});
+        }
+        let evm_tx_hash = validate_evm_tx_hash(evm_tx_hash)?;
+        let mut id = EXECUTION_SEQ.may_load(deps.storage)?.unwrap_or_default();
+        let mut confirmed: Vec<String> = vec![];
+        for (index, key) in keys.into_iter().enumerate() {
+            let (bot, key) = match (&action, key) {
+                (Action::Repay, CooldownKey::Repay { bot }) => {
+                    let bot = parse_address("keys", index, bot.as_str(), state.enforce_checksum)?;
+                    let cooldown = pending_submission(
+                        REPAY_COOLDOWNS.may_load(deps.storage, bot.0)?,
+                        index,
+                        success,
+                    )?;
+                    REPAY_COOLDOWNS.save(deps.storage, bot.0, &cooldown)?;
+                    if success {
+                        BOT_ATTEMPTS.remove(deps.storage, bot.0);
+                        QUARANTINED_BOTS.remove(deps.storage, bot.0);
+                    }
+                    let bot_key = canonical_address(&bot);
+                    (bot.0.to_vec(), CooldownKey::Repay { bot: bot_key })
+                }
+                (
+                    Action::CreateNextBot,
+                    CooldownKey::CreateNextBot {
+                        bot_id,
+                        remaining_count,
+                    },
+                ) => {
+                    let key: (&[u8], &[u8]) =
+                        (&bot_id.to_be_bytes(), &remaining_count.to_be_bytes());
+                    let cooldown = pending_submission(
+                        CREATE_NEXT_BOT_COOLDOWNS.may_load(deps.storage, key)?,
+                        index,
+                        success,
+                    )?;
+                    if success {
+                        CREATE_NEXT_BOT_COOLDOWNS.remove(deps.storage, key)?;
+                        EXECUTED_NEXT_BOTS.save(deps.storage, key, &cooldown)?;
+                    } else {
+                        CREATE_NEXT_BOT_COOLDOWNS.save(deps.storage, key, &cooldown)?;
+                    }
+                    (
+                        bot_id.to_be_bytes().to_vec(),
+                        CooldownKey::CreateNextBot {
+                            bot_id,
+                            remaining_count,
+                        },
+                    )
+                }
+                _ => {
+                    return Err(ActionKeyMismatch {
+                        action: action.as_str().to_string(),
+                        index,
+                    })
+                }
+            };
+            confirmed.push(match &key {
+                CooldownKey::Repay { bot } => bot.clone(),
+                CooldownKey::CreateNextBot {
+                    bot_id,
+                    remaining_count,
+                } => format!("{bot_id}:{remaining_count}"),
+            });
+            id += 1;
+            EXECUTION_HISTORY.save(
+                deps.storage,
+                (action.as_str(), &bot, id),
+                &ExecutionRecord {
+                    key,
+                    success,
+                    evm_tx_hash: evm_tx_hash.clone(),
+                    reporter: info.sender.clone(),
+                    reported_at: env.block.time,
+                },
+            )?;
+        }
+        EXECUTION_SEQ.save(deps.storage, &id)?;
+        Ok(Response::new()
+            .add_attribute("action", "confirm_execution")
+            .add_attribute("execution_action", action.as_str())
+            .add_attribute("success", success.to_string())
+            .add_attribute("evm_tx_hash", evm_tx_hash)
+            .add_attribute("keys", confirmed.join(",")))
+    }
+
+    pub fn update_retry_policy(
+        deps: DepsMut,
+        info: MessageInfo,
+        backoff: BackoffPolicy,
+        max_attempts: Option<u32>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let mut state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        state.backoff = backoff;
+        state.max_attempts = validate_max_attempts(max_attempts)?;
+        STATE.save(deps.storage, &state)?;
+        Ok(Response::new()
+            .add_attribute("action", "update_retry_policy")
+            .add_attribute(
+                "max_attempts",
+                state
+                    .max_attempts
+                    .map(|max| max.to_string())
+                    .unwrap_or_default(),
+            ))
+    }
+
+    pub fn reset_bot_attempts(
+        deps: DepsMut,
+        info: MessageInfo,
+        bot: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let bot_address = parse_address("bot", 0, bot.as_str(), state.enforce_checksum)?;
+        BOT_ATTEMPTS.remove(deps.storage, bot_address.0);
+        QUARANTINED_BOTS.remove(deps.storage, bot_address.0);
+        Ok(Response::new()
+            .add_attribute("action", "reset_bot_attempts")
+            .add_attribute("bot", canonical_address(&bot_address)))
+    }
+
+    pub fn raw_call(
+        deps: DepsMut,
+        info: MessageInfo,
+        function: String,
+        args: Vec<AbiValue>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        if !RAW_CALL_ALLOWLIST.has(deps.storage, function.as_str()) {
+            return Err(RawCallNotAllowed { function });
+        }
+        let contract = abi::load(deps.storage)?;
+        let abi_function =
+            contract
+                .function(function.as_str())
+                .map_err(|_| AbiFunctionNotFound {
+                    function: function.clone(),
+                })?;
+        if args.len() != abi_function.inputs.len() {
+            return Err(AbiEncoding {
+                function,
+                reason: format!(
+                    "expected {} arguments, got {}",
+                    abi_function.inputs.len(),
+                    args.len()
+                ),
+            });
+        }
+        let tokens = args
+            .into_iter()
+            .zip(abi_function.inputs.iter())
+            .enumerate()
+            .map(|(index, (value, param))| {
+                abi::value_token(
+                    &format!("args[{index}]"),
+                    value,
+                    &param.kind,
+                    state.enforce_checksum,
+                )
+            })
+            .collect::<Result<Vec<Token>, ContractError>>()?;
+        let payload = abi_function
+            .encode_input(&tokens)
+            .map_err(|e| AbiEncoding {
+                function: function.clone(),
+                reason: e.to_string(),
+            })?;
         Ok(Response::new()
             .add_message(CosmosMsg::Custom(PalomaMsg {
                 job_id: state.job_id,
-                payload: Binary::new(
-                    contract
-                        .function("update_service_fee")
-                        .unwrap()
-                        .encode_input(&[Token::Uint(Uint::from_big_endian(
-                            &new_service_fee.to_be_bytes(),
-                        ))])
-                        .unwrap(),
-                ),
+                payload: Binary::new(payload),
                 metadata: state.metadata,
             }))
-            .add_attribute("action", "update_service_fee"))
+            .add_attribute("action", "raw_call")
+            .add_attribute("function", function))
+    }
+
+    pub fn add_raw_call_function(
+        deps: DepsMut,
+        info: MessageInfo,
+        function: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        abi::load(deps.storage)?
+            .function(function.as_str())
+            .map_err(|_| AbiFunctionNotFound {
+                function: function.clone(),
+            })?;
+        RAW_CALL_ALLOWLIST.save(deps.storage, function.as_str(), &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "add_raw_call_function")
+            .add_attribute("function", function))
+    }
+
+    pub fn remove_raw_call_function(
+        deps: DepsMut,
+        info: MessageInfo,
+        function: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        RAW_CALL_ALLOWLIST.remove(deps.storage, function.as_str());
+        Ok(Response::new()
+            .add_attribute("action", "remove_raw_call_function")
+            .add_attribute("function", function))
+    }
+
+    pub fn update_abi(
+        deps: DepsMut,
+        info: MessageInfo,
+        new_abi: String,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let functions = abi::validate(&new_abi)?.functions().count();
+        ABI.save(deps.storage, &new_abi)?;
+        Ok(Response::new()
+            .add_attribute("action", "update_abi")
+            .add_attribute("functions", functions.to_string()))
+    }
+
+    pub fn update_retry_delay(
+        deps: DepsMut,
+        info: MessageInfo,
+        action: Action,
+        retry_delay: Option<u64>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let old_retry_delay = action_delay(deps.storage, &state, &action)?;
+        match retry_delay {
+            Some(retry_delay) => RETRY_DELAYS.save(
+                deps.storage,
+                action.as_str(),
+                &validate_retry_delay(retry_delay)?,
+            )?,
+            None => RETRY_DELAYS.remove(deps.storage, action.as_str()),
+        }
+        Ok(Response::new()
+            .add_attribute("action", "update_retry_delay")
+            .add_attribute("retry_action", action.as_str())
+            .add_attribute("old_retry_delay", old_retry_delay.to_string())
+            .add_attribute(
+                "new_retry_delay",
+                action_delay(deps.storage, &state, &action)?.to_string(),
+            ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        job_id: Option<String>,
+        retry_delay: Option<u64>,
+        creator: Option<String>,
+        signers: Option<Vec<String>>,
+        enforce_checksum: Option<bool>,
+        max_price_age: Option<u64>,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let mut state = STATE.load(deps.storage)?;
+        if state.owner.as_ref() != Some(&info.sender) {
+            return Err(Unauthorized {});
+        }
+        let mut response = Response::new().add_attribute("action", "update_config");
+        if let Some(job_id) = job_id {
+            let job_id = validate_job_id(job_id)?;
+            response = response
+                .add_attribute("old_job_id", state.job_id.as_str())
+                .add_attribute("new_job_id", job_id.as_str());
+            state.job_id = job_id;
+        }
+        if let Some(retry_delay) = retry_delay {
+            let retry_delay = validate_retry_delay(retry_delay)?;
+            response = response
+                .add_attribute("old_retry_delay", state.retry_delay.to_string())
+                .add_attribute("new_retry_delay", retry_delay.to_string());
+            state.retry_delay = retry_delay;
+        }
+        if let Some(creator) = creator {
+            response = response
+                .add_attribute("old_creator", state.metadata.creator.as_str())
+                .add_attribute("new_creator", creator.as_str());
+            state.metadata.creator = creator;
+        }
+        if let Some(signers) = signers {
+            let signers = dedup_signers(signers);
+            response = response
+                .add_attribute("old_signers", state.metadata.signers.join(","))
+                .add_attribute("new_signers", signers.join(","));
+            state.metadata.signers = signers;
+        }
+        if let Some(enforce_checksum) = enforce_checksum {
+            response = response
+                .add_attribute("old_enforce_checksum", state.enforce_checksum.to_string())
+                .add_attribute("new_enforce_checksum", enforce_checksum.to_string());
+            state.enforce_checksum = enforce_checksum;
+        }
+        if let Some(max_price_age) = max_price_age {
+            let max_price_age = validate_max_price_age(max_price_age)?;
+            response = response
+                .add_attribute("old_max_price_age", state.max_price_age.to_string())
+                .add_attribute("new_max_price_age", max_price_age.to_string());
+            state.max_price_age = max_price_age;
+        }
+        STATE.save(deps.storage, &state)?;
+        Ok(response)
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetJobId {} => to_json_binary(&query::get_job_id(deps)?),
+        QueryMsg::GetConfig {} => to_json_binary(&query::get_config(deps)?),
+        QueryMsg::GetOwnership {} => to_json_binary(&query::get_ownership(deps)?),
+        QueryMsg::GetCooldown { bot } => to_json_binary(&query::get_cooldown(deps, env, bot)?),
+        QueryMsg::GetCreateNextBotCooldown {
+            bot_id,
+            remaining_count,
+        } => to_json_binary(&query::get_create_next_bot_cooldown(
+            deps,
+            env,
+            bot_id,
+            remaining_count,
+        )?),
+        QueryMsg::ListCooldowns { start_after, limit } => {
+            to_json_binary(&query::list_cooldowns(deps, env, start_after, limit)?)
+        }
+        QueryMsg::ListQuarantinedBots { start_after, limit } => {
+            to_json_binary(&query::list_quarantined_bots(deps, start_after, limit)?)
+        }
+        QueryMsg::GetRepayHistory {
+            bot,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_repay_history(deps, bot, start_after, limit)?),
+        QueryMsg::GetCreateNextBotHistory {
+            bot_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_create_next_bot_history(
+            deps,
+            bot_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetBot { bot } => to_json_binary(&query::get_bot(deps, bot)?),
+        QueryMsg::ListBots {
+            filter,
+            start_after,
+            limit,
+        } => to_json_binary(&query::list_bots(deps, filter, start_after, limit)?),
+        QueryMsg::ListCallbackers { start_after, limit } => {
+            to_json_binary(&query::list_callbackers(deps, start_after, limit)?)
+        }
+        QueryMsg::ListSwapAddresses {
+            kind,
+            start_after,
+            limit,
+        } => to_json_binary(&query::list_swap_addresses(deps, kind, start_after, limit)?),
+        QueryMsg::GetSlippage {
+            token_in,
+            token_out,
+        } => to_json_binary(&query::get_slippage(deps, token_in, token_out)?),
+        QueryMsg::ListRawCallFunctions { start_after, limit } => {
+            to_json_binary(&query::list_raw_call_functions(deps, start_after, limit)?)
+        }
+        QueryMsg::SimulateRepayBot {
+            bot_info,
+            pad_swap_info,
+        } => to_json_binary(&query::simulate_repay_bot(
+            deps,
+            env,
+            bot_info,
+            pad_swap_info.unwrap_or(false),
+        )?),
+        QueryMsg::SimulateCreateNextBot {
+            bot_id,
+            callbacker,
+            callback_args,
+            remaining_count,
+        } => to_json_binary(&query::simulate_create_next_bot(
+            deps,
+            env,
+            bot_id,
+            callbacker,
+            callback_args,
+            remaining_count,
+        )?),
+        QueryMsg::GetRoleHolders {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&query::get_role_holders(deps, role, start_after, limit)?),
     }
 }
 
 pub mod query {
     use super::*;
+    use crate::address::{canonical_address, parse_address};
+    use crate::msg::{
+        BotFilter, BotInfo, BotResponse, CooldownEntry, CooldownKey, ExecutionEntry,
+        ExecutionHistoryResponse, GetConfigResponse, GetOwnershipResponse, GetRoleHoldersResponse,
+        ListBotsResponse, ListCallbackersResponse, ListCooldownsResponse,
+        ListQuarantinedBotsResponse, ListRawCallFunctionsResponse, ListSwapAddressesResponse,
+        QuarantinedBot, Role, SimulateCreateNextBotResponse, SimulateRepayBotResponse,
+        SlippageResponse, SwapAddressKind, ACTIONS,
+    };
+    use crate::state::{
+        Bot, BOTS, CALLBACKER_ALLOWLIST, CREATE_NEXT_BOT_COOLDOWNS, EXECUTION_HISTORY,
+        MAX_SLIPPAGE, OPERATORS, ORACLES, PENDING_OWNER, QUARANTINED_BOTS, RAW_CALL_ALLOWLIST,
+        REFERENCE_PRICES, REPORTERS, RETRY_DELAYS,
+    };
+    use cosmwasm_std::{Addr, HexBinary, Order, StdError, Uint256};
+    use cw_storage_plus::Bound;
+    use ethabi::Address;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
 
     pub fn get_job_id(deps: Deps) -> StdResult<GetJobIdResponse> {
         let state = STATE.load(deps.storage)?;
@@ -755,4 +1983,1054 @@ pub mod query {
             job_id: state.job_id,
         })
     }
+
+    pub fn get_config(deps: Deps) -> StdResult<GetConfigResponse> {
+        let retry_delays = ACTIONS
+            .iter()
+            .filter_map(|action| {
+                RETRY_DELAYS
+                    .may_load(deps.storage, action.as_str())
+                    .map(|delay| delay.map(|delay| (action.clone(), delay)))
+                    .transpose()
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(GetConfigResponse {
+            state: STATE.load(deps.storage)?,
+            retry_delays,
+            version: get_contract_version(deps.storage)?,
+        })
+    }
+
+    pub fn get_ownership(deps: Deps) -> StdResult<GetOwnershipResponse> {
+        let state = STATE.load(deps.storage)?;
+        let pending_owner = PENDING_OWNER.may_load(deps.storage)?;
+        Ok(GetOwnershipResponse {
+            owner: state.owner,
+            pending_owner: pending_owner.as_ref().map(|pending| pending.owner.clone()),
+            pending_expires_at: pending_owner.and_then(|pending| pending.expires_at),
+        })
+    }
+
+    pub fn get_cooldown(deps: Deps, env: Env, bot: String) -> StdResult<CooldownResponse> {
+        let bot = parse_bot(bot)?.0;
+        Ok(cooldown_status(
+            REPAY_COOLDOWNS.may_load(deps.storage, bot)?,
+            &env.block.time,
+        ))
+    }
+
+    pub fn get_create_next_bot_cooldown(
+        deps: Deps,
+        env: Env,
+        bot_id: Uint256,
+        remaining_count: Uint256,
+    ) -> StdResult<CooldownResponse> {
+        create_next_bot_status(deps.storage, bot_id, remaining_count, &env.block.time)
+    }
+
+    fn parse_bot(bot: String) -> StdResult<Address> {
+        parse_address("bot", 0, bot.as_str(), false)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+
+    fn to_uint256(bytes: Vec<u8>) -> StdResult<Uint256> {
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| StdError::generic_err("invalid cooldown key"))?;
+        Ok(Uint256::from_be_bytes(bytes))
+    }
+
+    /// Lists `RepayBot` cooldowns first, then `CreateNextBot` cooldowns.
+    pub fn list_cooldowns(
+        deps: Deps,
+        env: Env,
+        start_after: Option<CooldownKey>,
+        limit: Option<u32>,
+    ) -> StdResult<ListCooldownsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let mut cooldowns: Vec<CooldownEntry> = vec![];
+        let create_next_bot_start = match start_after {
+            Some(CooldownKey::CreateNextBot {
+                bot_id,
+                remaining_count,
+            }) => Some((bot_id.to_be_bytes(), remaining_count.to_be_bytes())),
+            repay_start => {
+                let start = match repay_start {
+                    Some(CooldownKey::Repay { bot }) => Some(Bound::exclusive(parse_bot(bot)?.0)),
+                    _ => None,
+                };
+                for item in REPAY_COOLDOWNS
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(limit)
+                {
+                    let (bot, cooldown) = item?;
+                    cooldowns.push(CooldownEntry {
+                        key: CooldownKey::Repay {
+                            bot: canonical_address(&Address::from(bot)),
+                        },
+                        cooldown: cooldown_status(Some(cooldown), &env.block.time),
+                    });
+                }
+                None
+            }
+        };
+        let start = create_next_bot_start
+            .as_ref()
+            .map(|(bot_id, remaining_count)| {
+                Bound::exclusive((bot_id.as_slice(), remaining_count.as_slice()))
+            });
+        for item in CREATE_NEXT_BOT_COOLDOWNS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit - cooldowns.len())
+        {
+            let ((bot_id, remaining_count), cooldown) = item?;
+            cooldowns.push(CooldownEntry {
+                key: CooldownKey::CreateNextBot {
+                    bot_id: to_uint256(bot_id)?,
+                    remaining_count: to_uint256(remaining_count)?,
+                },
+                cooldown: cooldown_status(Some(cooldown), &env.block.time),
+            });
+        }
+        Ok(ListCooldownsResponse { cooldowns })
+    }
+
+    pub fn list_quarantined_bots(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListQuarantinedBotsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = match start_after {
+            Some(bot) => Some(Bound::exclusive(parse_bot(bot)?.0)),
+            None => None,
+        };
+        let bots = QUARANTINED_BOTS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (bot, quarantined_at) = item?;
+                Ok(QuarantinedBot {
+                    bot: canonical_address(&Address::from(bot)),
+                    attempts: BOT_ATTEMPTS
+                        .may_load(deps.storage, bot)?
+                        .unwrap_or_default(),
+                    quarantined_at,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(ListQuarantinedBotsResponse { bots })
+    }
+
+    pub fn get_bot(deps: Deps, bot: String) -> StdResult<BotResponse> {
+        let bot = parse_bot(bot)?;
+        Ok(BotResponse {
+            bot: canonical_address(&bot),
+            info: BOTS.load(deps.storage, bot.0)?,
+        })
+    }
+
+    pub fn list_bots(
+        deps: Deps,
+        filter: Option<BotFilter>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListBotsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = match start_after {
+            Some(bot) => Some(Bound::exclusive(parse_bot(bot)?.0)),
+            None => None,
+        };
+        let bots = match filter {
+            None => BOTS.range(deps.storage, start, None, Order::Ascending),
+            Some(BotFilter::Owner { owner }) => BOTS.idx.owner.prefix(parse_key(owner)?).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            ),
+            Some(BotFilter::Callbacker { callbacker }) => BOTS
+                .idx
+                .callbacker
+                .prefix(parse_key(callbacker)?)
+                .range(deps.storage, start, None, Order::Ascending),
+            Some(BotFilter::Status { status }) => BOTS
+                .idx
+                .status
+                .prefix(status.as_str().to_string())
+                .range(deps.storage, start, None, Order::Ascending),
+        }
+        .take(limit)
+        .map(|item| {
+            item.map(|(bot, info): ([u8; 20], Bot)| BotResponse {
+                bot: canonical_address(&Address::from(bot)),
+                info,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+        Ok(ListBotsResponse { bots })
+    }
+
+    /// Canonical form of an address used as an index key.
+    fn parse_key(address: String) -> StdResult<String> {
+        Ok(canonical_address(&parse_bot(address)?))
+    }
+
+    pub fn get_repay_history(
+        deps: Deps,
+        bot: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ExecutionHistoryResponse> {
+        let bot = parse_bot(bot)?.0;
+        execution_history(deps, Action::Repay, &bot, start_after, limit)
+    }
+
+    pub fn get_create_next_bot_history(
+        deps: Deps,
+        bot_id: Uint256,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ExecutionHistoryResponse> {
+        execution_history(
+            deps,
+            Action::CreateNextBot,
+            &bot_id.to_be_bytes(),
+            start_after,
+            limit,
+        )
+    }
+
+    /// Lists the records of one bot newest first, starting below `start_after`.
+    fn execution_history(
+        deps: Deps,
+        action: Action,
+        bot: &[u8],
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ExecutionHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let records = EXECUTION_HISTORY
+            .prefix((action.as_str(), bot))
+            .range(
+                deps.storage,
+                None,
+                start_after.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(id, record)| ExecutionEntry { id, record }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(ExecutionHistoryResponse { records })
+    }
+
+    pub fn list_callbackers(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListCallbackersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = match start_after {
+            Some(callbacker) => Some(Bound::exclusive(parse_bot(callbacker)?.0)),
+            None => None,
+        };
+        let callbackers = CALLBACKER_ALLOWLIST
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|callbacker| canonical_address(&Address::from(callbacker))))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(ListCallbackersResponse { callbackers })
+    }
+
+    pub fn list_swap_addresses(
+        deps: Deps,
+        kind: SwapAddressKind,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListSwapAddressesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = match start_after {
+            Some(address) => Some(Bound::exclusive(parse_bot(address)?.0)),
+            None => None,
+        };
+        let addresses = approved_swap_addresses(&kind)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|address| canonical_address(&Address::from(address))))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(ListSwapAddressesResponse { kind, addresses })
+    }
+
+    pub fn get_slippage(
+        deps: Deps,
+        token_in: String,
+        token_out: String,
+    ) -> StdResult<SlippageResponse> {
+        let token_in = parse_bot(token_in)?;
+        let token_out = parse_bot(token_out)?;
+        let pair = (token_in.as_bytes(), token_out.as_bytes());
+        Ok(SlippageResponse {
+            max_slippage_bps: MAX_SLIPPAGE.may_load(deps.storage, pair)?,
+            reference_price: REFERENCE_PRICES.may_load(deps.storage, pair)?,
+        })
+    }
+
+    pub fn list_raw_call_functions(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListRawCallFunctionsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let functions = RAW_CALL_ALLOWLIST
+            .keys(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<String>>>()?;
+        Ok(ListRawCallFunctionsResponse { functions })
+    }
+
+    fn selector(function: &str) -> StdResult<HexBinary> {
+        let function =
+            abi::function_by_name(function).map_err(|e| StdError::generic_err(e.to_string()))?;
+        Ok(HexBinary::from(function.short_signature().as_slice()))
+    }
+
+    /// Runs the `RepayBot` planning of the execute handler. Errors it would
+    /// fail with are returned in `error` rather than failing the query.
+    pub fn simulate_repay_bot(
+        deps: Deps,
+        env: Env,
+        bot_info: Vec<BotInfo>,
+        pad_swap_info: bool,
+    ) -> StdResult<SimulateRepayBotResponse> {
+        let state = STATE.load(deps.storage)?;
+        let mut response = SimulateRepayBotResponse {
+            selector: selector("repay_bot")?,
+            payload: None,
+            submitted: vec![],
+            skipped: vec![],
+            error: None,
+        };
+        match execute::plan_repay_bot(
+            deps.storage,
+            &state,
+            &env.block.time,
+            bot_info,
+            pad_swap_info,
+        ) {
+            Ok(plan) => {
+                response.payload = plan.payload.map(HexBinary::from);
+                response.submitted = plan.result.submitted;
+                response.skipped = plan.result.skipped;
+                if plan.bots.is_empty() && plan.quarantined.is_empty() {
+                    response.error = Some(ContractError::AllPending {}.to_string());
+                }
+            }
+            Err(e) => response.error = Some(e.to_string()),
+        }
+        Ok(response)
+    }
+
+    /// Runs the `CreateNextBot` planning of the execute handler. Errors it
+    /// would fail with are returned in `error` rather than failing the query.
+    pub fn simulate_create_next_bot(
+        deps: Deps,
+        env: Env,
+        bot_id: Uint256,
+        callbacker: String,
+        callback_args: Vec<Uint256>,
+        remaining_count: Uint256,
+    ) -> StdResult<SimulateCreateNextBotResponse> {
+        let state = STATE.load(deps.storage)?;
+        let mut response = SimulateCreateNextBotResponse {
+            selector: selector("create_next_bot")?,
+            payload: None,
+            cooldown: None,
+            error: None,
+        };
+        match execute::plan_create_next_bot(
+            deps.storage,
+            &state,
+            &env.block.time,
+            bot_id,
+            callbacker,
+            callback_args,
+            remaining_count,
+        ) {
+            Ok(plan) => {
+                response.payload = plan.payload.map(HexBinary::from);
+                response.cooldown = Some(plan.cooldown);
+                if response.payload.is_none() {
+                    response.error = Some(ContractError::AllPending {}.to_string());
+                }
+            }
+            Err(e) => response.error = Some(e.to_string()),
+        }
+        Ok(response)
+    }
+
+    pub fn get_role_holders(
+        deps: Deps,
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<GetRoleHoldersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after.map(Addr::unchecked);
+        let holders: Vec<Addr> = match role {
+            Role::Admin => STATE
+                .load(deps.storage)?
+                .owner
+                .into_iter()
+                .filter(|owner| start_after.as_ref().is_none_or(|start| owner > start))
+                .collect(),
+            Role::Operator => OPERATORS
+                .keys(
+                    deps.storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<_>>()?,
+            Role::Oracle => ORACLES
+                .keys(
+                    deps.storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<_>>()?,
+            Role::Reporter => REPORTERS
+                .keys(
+                    deps.storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<_>>()?,
+        };
+        Ok(GetRoleHoldersResponse { role, holders })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{BotInfo, CooldownKey, RepayBotResult, SkipReason, SkippedBot, SwapInfo};
+    use crate::state::{BOT_ATTEMPTS, QUARANTINED_BOTS};
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        from_json, Attribute, Decimal256, HexBinary, Order, OwnedDeps, Record, Uint256,
+    };
+
+    type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    const CALLBACKER: u8 = 0x20;
+    const TOKEN_IN: u8 = 0x50;
+    const POOL: u8 = 0x51;
+    const TOKEN_OUT: u8 = 0x52;
+
+    fn address(n: u8) -> String {
+        format!("0x{n:040x}")
+    }
+
+    fn send(
+        deps: &mut TestDeps,
+        env: &Env,
+        msg: ExecuteMsg,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let owner = message_info(&deps.api.addr_make("owner"), &[]);
+        execute(deps.as_mut(), env.clone(), owner, msg)
+    }
+
+    /// Bots 1 to 3 registered with `CALLBACKER`, and a `TOKEN_IN` to
+    /// `TOKEN_OUT` route through `POOL` approved.
+    fn setup(backoff: BackoffPolicy, max_attempts: Option<u32>) -> (TestDeps, Env) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = message_info(&deps.api.addr_make("owner"), &[]);
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            owner,
+            InstantiateMsg {
+                retry_delay: 60,
+                job_id: "job".to_string(),
+                creator: "creator".to_string(),
+                signers: vec![],
+                enforce_checksum: None,
+                backoff: Some(backoff),
+                max_attempts,
+                max_price_age: None,
+                abi: None,
+            },
+        )
+        .unwrap();
+        let callbacker = address(CALLBACKER);
+        send(&mut deps, &env, ExecuteMsg::AddCallbacker { callbacker }).unwrap();
+        for (kind, addresses) in [
+            (
+                SwapAddressKind::Token,
+                vec![address(TOKEN_IN), address(TOKEN_OUT)],
+            ),
+            (SwapAddressKind::Pool, vec![address(POOL)]),
+        ] {
+            send(
+                &mut deps,
+                &env,
+                ExecuteMsg::AddSwapAddresses { kind, addresses },
+            )
+            .unwrap();
+        }
+        for bot in 1..=3 {
+            send(
+                &mut deps,
+                &env,
+                ExecuteMsg::RegisterBot {
+                    bot: address(bot),
+                    owner: address(0x10),
+                    callbacker: address(CALLBACKER),
+                    callback_args: vec![],
+                    collateral: address(0x30),
+                    market: address(0x40),
+                },
+            )
+            .unwrap();
+        }
+        (deps, env)
+    }
+
+    fn swap_info(amount: u32, expected: u32) -> SwapInfo {
+        let mut route = vec![address(0); SWAP_ROUTE_LENGTH];
+        route[0] = address(TOKEN_IN);
+        route[1] = address(POOL);
+        route[2] = address(TOKEN_OUT);
+        SwapInfo {
+            route,
+            swap_params: vec![vec![Uint256::zero(); SWAP_PARAMS_LENGTH]; SWAP_PARAMS_LENGTH],
+            amount: Uint256::from(amount),
+            expected: Uint256::from(expected),
+            pools: vec![address(0); SWAP_POOLS_LENGTH],
+        }
+    }
+
+    fn bot_info(bot: u8, swap_info: SwapInfo) -> BotInfo {
+        BotInfo {
+            bot: address(bot),
+            callbacker: address(CALLBACKER),
+            callback_args: vec![],
+            swap_info,
+        }
+    }
+
+    fn repay(
+        deps: &mut TestDeps,
+        env: &Env,
+        bots: &[u8],
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        let bot_info = bots
+            .iter()
+            .map(|bot| bot_info(*bot, swap_info(1000, 1000)))
+            .collect();
+        send(
+            deps,
+            env,
+            ExecuteMsg::RepayBot {
+                bot_info,
+                pad_swap_info: None,
+            },
+        )
+    }
+
+    fn repay_result(response: &Response<PalomaMsg>) -> RepayBotResult {
+        from_json(response.data.as_ref().unwrap()).unwrap()
+    }
+
+    fn next_eligible(deps: &TestDeps, env: &Env, bot: u8) -> Option<Timestamp> {
+        query::get_cooldown(deps.as_ref(), env.clone(), address(bot))
+            .unwrap()
+            .next_eligible
+    }
+
+    /// Submits `bot` again as soon as its cooldown has passed, returning the
+    /// retry delay after each submission.
+    fn retry_delays(deps: &mut TestDeps, env: &mut Env, bot: u8, times: usize) -> Vec<u64> {
+        (0..times)
+            .map(|_| {
+                repay(deps, env, &[bot]).unwrap();
+                let next_eligible = next_eligible(deps, env, bot).unwrap();
+                let delay = next_eligible.seconds() - env.block.time.seconds();
+                env.block.time = next_eligible.plus_seconds(1);
+                delay
+            })
+            .collect()
+    }
+
+    fn bot_key(bot: u8) -> [u8; 20] {
+        let mut key = [0; 20];
+        key[19] = bot;
+        key
+    }
+
+    fn confirm(
+        deps: &mut TestDeps,
+        env: &Env,
+        action: Action,
+        keys: Vec<CooldownKey>,
+        success: bool,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        send(
+            deps,
+            env,
+            ExecuteMsg::ConfirmExecution {
+                action,
+                keys,
+                success,
+                evm_tx_hash: format!("0x{}", "ab".repeat(32)),
+            },
+        )
+    }
+
+    fn create_next_bot(
+        deps: &mut TestDeps,
+        env: &Env,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        send(
+            deps,
+            env,
+            ExecuteMsg::CreateNextBot {
+                bot_id: Uint256::from(7u8),
+                callbacker: address(CALLBACKER),
+                callback_args: vec![],
+                remaining_count: Uint256::from(3u8),
+            },
+        )
+    }
+
+    fn set_price(deps: &mut TestDeps, env: &Env, price: Decimal256) -> Result<(), ContractError> {
+        send(
+            deps,
+            env,
+            ExecuteMsg::SetReferencePrice {
+                token_in: address(TOKEN_IN),
+                token_out: address(TOKEN_OUT),
+                price,
+            },
+        )
+        .map(|_| ())
+    }
+
+    /// A 100 bps bound on the test route at a reference price of 2.
+    fn setup_slippage() -> (TestDeps, Env) {
+        let (mut deps, env) = setup(BackoffPolicy::Fixed, None);
+        send(
+            &mut deps,
+            &env,
+            ExecuteMsg::SetMaxSlippage {
+                token_in: address(TOKEN_IN),
+                token_out: address(TOKEN_OUT),
+                max_slippage_bps: Some(100),
+            },
+        )
+        .unwrap();
+        set_price(&mut deps, &env, Decimal256::from_ratio(2u8, 1u8)).unwrap();
+        (deps, env)
+    }
+
+    fn repay_swap(
+        deps: &mut TestDeps,
+        env: &Env,
+        bot: u8,
+        swap_info: SwapInfo,
+    ) -> Result<Response<PalomaMsg>, ContractError> {
+        send(
+            deps,
+            env,
+            ExecuteMsg::RepayBot {
+                bot_info: vec![bot_info(bot, swap_info)],
+                pad_swap_info: None,
+            },
+        )
+    }
+
+    #[test]
+    fn slippage_is_measured_in_bps_of_the_quote() {
+        let (mut deps, env) = setup_slippage();
+        // quote 2000, 1979 is 105 bps below it
+        let err = repay_swap(&mut deps, &env, 1, swap_info(1000, 1979)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::SlippageExceeded {
+                index: 0,
+                slippage_bps,
+                max_slippage_bps: 100,
+            } if slippage_bps == Uint256::from(105u32)
+        ));
+        // exactly 100 bps is allowed
+        repay_swap(&mut deps, &env, 1, swap_info(1000, 1980)).unwrap();
+        repay_swap(&mut deps, &env, 2, swap_info(1000, 2500)).unwrap();
+    }
+
+    #[test]
+    fn zero_prices_and_quotes_are_rejected() {
+        let (mut deps, env) = setup_slippage();
+        assert!(matches!(
+            set_price(&mut deps, &env, Decimal256::zero()).unwrap_err(),
+            ContractError::ZeroReferencePrice {}
+        ));
+        // 1000 * 0.0001 rounds down to a zero quote
+        set_price(&mut deps, &env, Decimal256::from_ratio(1u8, 10_000u32)).unwrap();
+        assert!(matches!(
+            repay_swap(&mut deps, &env, 1, swap_info(1000, 0)).unwrap_err(),
+            ContractError::ZeroReferenceQuote { index: 0 }
+        ));
+    }
+
+    #[test]
+    fn stale_prices_are_rejected_for_submitted_bots_only() {
+        let (mut deps, mut env) = setup_slippage();
+        send(
+            &mut deps,
+            &env,
+            ExecuteMsg::UpdateConfig {
+                job_id: None,
+                retry_delay: None,
+                creator: None,
+                signers: None,
+                enforce_checksum: None,
+                max_price_age: Some(30),
+            },
+        )
+        .unwrap();
+        repay_swap(&mut deps, &env, 1, swap_info(1000, 2000)).unwrap();
+        env.block.time = env.block.time.plus_seconds(31);
+        // bot 1 is still in cooldown, so its stale price does not matter
+        assert!(matches!(
+            repay_swap(&mut deps, &env, 1, swap_info(1000, 2000)).unwrap_err(),
+            ContractError::AllPending {}
+        ));
+        assert!(matches!(
+            repay_swap(&mut deps, &env, 2, swap_info(1000, 2000)).unwrap_err(),
+            ContractError::StaleReferencePrice {
+                index: 0,
+                max_price_age: 30,
+                ..
+            }
+        ));
+        set_price(&mut deps, &env, Decimal256::from_ratio(2u8, 1u8)).unwrap();
+        repay_swap(&mut deps, &env, 2, swap_info(1000, 2000)).unwrap();
+    }
+
+    #[test]
+    fn linear_backoff_grows_with_attempts() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Linear, None);
+        assert_eq!(
+            retry_delays(&mut deps, &mut env, 1, 4),
+            vec![60, 120, 180, 240]
+        );
+    }
+
+    #[test]
+    fn exponential_backoff_is_capped() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Exponential { max_delay: 300 }, None);
+        assert_eq!(
+            retry_delays(&mut deps, &mut env, 1, 5),
+            vec![60, 120, 240, 300, 300]
+        );
+    }
+
+    #[test]
+    fn quarantines_after_max_attempts_once_the_cooldown_has_passed() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Fixed, Some(2));
+        retry_delays(&mut deps, &mut env, 1, 1);
+        repay(&mut deps, &env, &[1]).unwrap();
+
+        // max_attempts reached, but the last submission is still pending
+        env.block.time = env.block.time.plus_seconds(30);
+        assert!(matches!(
+            repay(&mut deps, &env, &[1]).unwrap_err(),
+            ContractError::AllPending {}
+        ));
+        assert!(QUARANTINED_BOTS.is_empty(&deps.storage));
+
+        // quarantining is a change of state, so the call succeeds without a
+        // submission
+        env.block.time = env.block.time.plus_seconds(31);
+        let response = repay(&mut deps, &env, &[1]).unwrap();
+        assert!(response.messages.is_empty());
+        assert!(QUARANTINED_BOTS.has(&deps.storage, bot_key(1)));
+
+        let response = repay(&mut deps, &env, &[1, 2]).unwrap();
+        assert_eq!(response.messages.len(), 1);
+        let result = repay_result(&response);
+        assert_eq!(result.submitted, vec![address(2)]);
+        assert_eq!(
+            result.skipped,
+            vec![SkippedBot {
+                bot: address(1),
+                reason: SkipReason::Quarantined,
+                next_eligible: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn reset_bot_attempts_releases_the_bot() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Linear, Some(1));
+        retry_delays(&mut deps, &mut env, 1, 1);
+        repay(&mut deps, &env, &[1]).unwrap();
+        assert!(matches!(
+            repay(&mut deps, &env, &[1]).unwrap_err(),
+            ContractError::AllPending {}
+        ));
+
+        send(
+            &mut deps,
+            &env,
+            ExecuteMsg::ResetBotAttempts { bot: address(1) },
+        )
+        .unwrap();
+        let response = repay(&mut deps, &env, &[1]).unwrap();
+        assert_eq!(repay_result(&response).submitted, vec![address(1)]);
+        // the backoff starts over
+        assert_eq!(
+            next_eligible(&deps, &env, 1),
+            Some(env.block.time.plus_seconds(60))
+        );
+    }
+
+    #[test]
+    fn a_bot_listed_twice_is_submitted_once() {
+        let (mut deps, env) = setup(BackoffPolicy::Linear, None);
+        let response = repay(&mut deps, &env, &[1, 2, 1]).unwrap();
+        let result = repay_result(&response);
+        assert_eq!(result.submitted, vec![address(1), address(2)]);
+        assert_eq!(
+            result.skipped,
+            vec![SkippedBot {
+                bot: address(1),
+                reason: SkipReason::Cooldown,
+                next_eligible: Some(env.block.time.plus_seconds(60)),
+            }]
+        );
+    }
+
+    #[test]
+    fn prunes_only_expired_cooldowns() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Linear, None);
+        // bot 1 in backoff for 120 seconds, bots 2 and 3 for 60
+        retry_delays(&mut deps, &mut env, 1, 1);
+        repay(&mut deps, &env, &[1, 2, 3]).unwrap();
+        env.block.time = env.block.time.plus_seconds(61);
+
+        let response = send(
+            &mut deps,
+            &env,
+            ExecuteMsg::PruneCooldowns { limit: Some(1) },
+        )
+        .unwrap();
+        assert!(response.attributes.contains(&Attribute::new("pruned", "1")));
+        let response = send(&mut deps, &env, ExecuteMsg::PruneCooldowns { limit: None }).unwrap();
+        assert!(response.attributes.contains(&Attribute::new("pruned", "1")));
+        assert!(next_eligible(&deps, &env, 1).is_some());
+        assert_eq!(next_eligible(&deps, &env, 2), None);
+        assert_eq!(next_eligible(&deps, &env, 3), None);
+    }
+
+    #[test]
+    fn only_pending_submissions_can_be_confirmed() {
+        let (mut deps, env) = setup(BackoffPolicy::Fixed, None);
+        let keys = |bots: &[u8]| {
+            bots.iter()
+                .map(|bot| CooldownKey::Repay { bot: address(*bot) })
+                .collect::<Vec<_>>()
+        };
+        assert!(matches!(
+            confirm(&mut deps, &env, Action::Repay, keys(&[1]), true).unwrap_err(),
+            ContractError::NoPendingSubmission { index: 0 }
+        ));
+        repay(&mut deps, &env, &[1]).unwrap();
+        assert!(matches!(
+            confirm(&mut deps, &env, Action::Repay, keys(&[2, 1]), true).unwrap_err(),
+            ContractError::NoPendingSubmission { index: 0 }
+        ));
+        confirm(&mut deps, &env, Action::Repay, keys(&[1]), true).unwrap();
+        assert!(matches!(
+            confirm(&mut deps, &env, Action::Repay, keys(&[1]), false).unwrap_err(),
+            ContractError::NoPendingSubmission { index: 0 }
+        ));
+        let cooldown = query::get_cooldown(deps.as_ref(), env.clone(), address(1)).unwrap();
+        assert_eq!(cooldown.confirmed, Some(true));
+        assert!(!cooldown.eligible);
+    }
+
+    #[test]
+    fn a_failed_execution_keeps_the_backoff() {
+        let (mut deps, env) = setup(BackoffPolicy::Linear, Some(3));
+        repay(&mut deps, &env, &[1]).unwrap();
+        let next = next_eligible(&deps, &env, 1);
+        confirm(
+            &mut deps,
+            &env,
+            Action::Repay,
+            vec![CooldownKey::Repay { bot: address(1) }],
+            false,
+        )
+        .unwrap();
+        let cooldown = query::get_cooldown(deps.as_ref(), env.clone(), address(1)).unwrap();
+        assert_eq!(cooldown.next_eligible, next);
+        assert_eq!(cooldown.confirmed, Some(false));
+        assert_eq!(BOT_ATTEMPTS.load(&deps.storage, bot_key(1)).unwrap(), 1);
+        assert!(matches!(
+            repay(&mut deps, &env, &[1]).unwrap_err(),
+            ContractError::AllPending {}
+        ));
+    }
+
+    #[test]
+    fn an_executed_next_bot_is_not_submitted_again() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Fixed, None);
+        create_next_bot(&mut deps, &env).unwrap();
+        let key = CooldownKey::CreateNextBot {
+            bot_id: Uint256::from(7u8),
+            remaining_count: Uint256::from(3u8),
+        };
+        confirm(
+            &mut deps,
+            &env,
+            Action::CreateNextBot,
+            vec![key.clone()],
+            true,
+        )
+        .unwrap();
+
+        env.block.time = env.block.time.plus_seconds(61);
+        send(&mut deps, &env, ExecuteMsg::PruneCooldowns { limit: None }).unwrap();
+        assert!(matches!(
+            create_next_bot(&mut deps, &env).unwrap_err(),
+            ContractError::AllPending {}
+        ));
+        let cooldown = query::get_create_next_bot_cooldown(
+            deps.as_ref(),
+            env.clone(),
+            Uint256::from(7u8),
+            Uint256::from(3u8),
+        )
+        .unwrap();
+        assert_eq!(
+            cooldown,
+            CooldownResponse {
+                last_submitted: Some(mock_env().block.time),
+                next_eligible: None,
+                eligible: false,
+                confirmed: Some(true),
+            }
+        );
+        assert!(matches!(
+            confirm(&mut deps, &env, Action::CreateNextBot, vec![key], false).unwrap_err(),
+            ContractError::NoPendingSubmission { index: 0 }
+        ));
+    }
+
+    fn paloma_payload(response: &Response<PalomaMsg>) -> HexBinary {
+        match &response.messages[..] {
+            [message] => match &message.msg {
+                CosmosMsg::Custom(msg) => HexBinary::from(msg.payload.as_slice()),
+                msg => panic!("unexpected message {msg:?}"),
+            },
+            messages => panic!("expected one message, got {}", messages.len()),
+        }
+    }
+
+    fn storage_snapshot(deps: &TestDeps) -> Vec<Record> {
+        deps.storage.range(None, None, Order::Ascending).collect()
+    }
+
+    #[test]
+    fn simulate_repay_bot_matches_repay_bot() {
+        let (mut deps, mut env) = setup(BackoffPolicy::Linear, Some(1));
+        // bot 1 quarantined, bot 2 in cooldown, bot 3 submitted
+        repay(&mut deps, &env, &[1]).unwrap();
+        env.block.time = env.block.time.plus_seconds(61);
+        repay(&mut deps, &env, &[2]).unwrap();
+        let bot_info = [1, 2, 3, 3]
+            .map(|bot| bot_info(bot, swap_info(1000, 1000)))
+            .to_vec();
+
+        let before = storage_snapshot(&deps);
+        let simulated =
+            query::simulate_repay_bot(deps.as_ref(), env.clone(), bot_info.clone(), false).unwrap();
+        assert_eq!(storage_snapshot(&deps), before);
+
+        let response = send(
+            &mut deps,
+            &env,
+            ExecuteMsg::RepayBot {
+                bot_info,
+                pad_swap_info: None,
+            },
+        )
+        .unwrap();
+        let result = repay_result(&response);
+        assert_eq!(simulated.error, None);
+        assert_eq!(simulated.payload, Some(paloma_payload(&response)));
+        assert_eq!(simulated.submitted, result.submitted);
+        assert_eq!(simulated.skipped, result.skipped);
+        assert_eq!(
+            simulated
+                .skipped
+                .iter()
+                .map(|skipped| skipped.reason.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                SkipReason::Quarantined,
+                SkipReason::Cooldown,
+                SkipReason::Cooldown
+            ]
+        );
+    }
+
+    #[test]
+    fn simulate_create_next_bot_matches_create_next_bot() {
+        let (mut deps, env) = setup(BackoffPolicy::Fixed, None);
+        let simulate = |deps: &TestDeps| {
+            query::simulate_create_next_bot(
+                deps.as_ref(),
+                env.clone(),
+                Uint256::from(7u8),
+                address(CALLBACKER),
+                vec![],
+                Uint256::from(3u8),
+            )
+            .unwrap()
+        };
+
+        let before = storage_snapshot(&deps);
+        let simulated = simulate(&deps);
+        assert_eq!(storage_snapshot(&deps), before);
+        let response = create_next_bot(&mut deps, &env).unwrap();
+        assert_eq!(simulated.error, None);
+        assert_eq!(simulated.payload, Some(paloma_payload(&response)));
+
+        // in cooldown at the same block
+        let simulated = simulate(&deps);
+        assert_eq!(simulated.payload, None);
+        assert_eq!(
+            simulated.error,
+            Some(create_next_bot(&mut deps, &env).unwrap_err().to_string())
+        );
+        assert_eq!(
+            simulated.cooldown.map(|cooldown| cooldown.eligible),
+            Some(false)
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint256};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,11 +6,158 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("AllPending")]
     AllPending {},
+
+    #[error("EmptyBotInfo")]
+    EmptyBotInfo {},
+
+    #[error("Invalid EVM address in {field} at index {index}: {value}")]
+    InvalidEvmAddress {
+        field: String,
+        index: usize,
+        value: String,
+    },
+
+    #[error("Invalid EIP-55 checksum in {field} at index {index}: {value}")]
+    InvalidEvmChecksum {
+        field: String,
+        index: usize,
+        value: String,
+    },
+
+    #[error("Invalid {field} length at index {index}: expected {expected}, got {actual}")]
+    InvalidSwapInfo {
+        field: String,
+        index: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("ABI function not found: {function}")]
+    AbiFunctionNotFound { function: String },
+
+    #[error("ABI encoding failed for {function}: {reason}")]
+    AbiEncoding { function: String, reason: String },
+
+    #[error("Argument {path} does not match ABI type {expected}")]
+    AbiValueMismatch { path: String, expected: String },
+
+    #[error("Function {function} is not allowed in RawCall")]
+    RawCallNotAllowed { function: String },
+
+    #[error("Invalid ABI: {reason}")]
+    InvalidAbi { reason: String },
+
+    #[error("ABI function {function} is {actual}, expected {expected}")]
+    AbiSignatureMismatch {
+        function: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Cannot migrate from contract {contract}")]
+    CannotMigrateContract { contract: String },
+
+    #[error("Cannot migrate from version {previous} to older version {current}")]
+    CannotMigrateVersion { previous: String, current: String },
+
+    #[error("NoPendingOwner")]
+    NoPendingOwner {},
+
+    #[error("OwnershipProposalExpired")]
+    OwnershipProposalExpired {},
+
+    #[error("EmptyJobId")]
+    EmptyJobId {},
+
+    #[error("ZeroRetryDelay")]
+    ZeroRetryDelay {},
+
+    #[error("ZeroMaxAttempts")]
+    ZeroMaxAttempts {},
+
+    #[error("ZeroMaxPriceAge")]
+    ZeroMaxPriceAge {},
+
+    #[error("EmptyExecutionKeys")]
+    EmptyExecutionKeys {},
+
+    #[error("Key {index} does not belong to action {action}")]
+    ActionKeyMismatch { action: String, index: usize },
+
+    #[error("Key {index} has no submission awaiting confirmation")]
+    NoPendingSubmission { index: usize },
+
+    #[error("Invalid EVM tx hash: {value}")]
+    InvalidEvmTxHash { value: String },
+
+    #[error("Bot {bot} at index {index} is not registered")]
+    UnknownBot { index: usize, bot: String },
+
+    #[error("Bot {bot} at index {index} is not active")]
+    BotNotActive { index: usize, bot: String },
+
+    #[error("Bot {bot} is already registered")]
+    BotAlreadyRegistered { bot: String },
+
+    #[error("{field} at index {index} is not an approved {kind}: {address}")]
+    SwapAddressNotApproved {
+        field: String,
+        index: usize,
+        kind: String,
+        address: String,
+    },
+
+    #[error("Slippage of {slippage_bps} bps at index {index} exceeds {max_slippage_bps} bps")]
+    SlippageExceeded {
+        index: usize,
+        slippage_bps: Uint256,
+        max_slippage_bps: u16,
+    },
+
+    #[error("No reference price for {token_in} to {token_out} at index {index}")]
+    MissingReferencePrice {
+        index: usize,
+        token_in: String,
+        token_out: String,
+    },
+
+    #[error("Reference price for {token_in} to {token_out} at index {index} is older than {max_price_age} seconds")]
+    StaleReferencePrice {
+        index: usize,
+        token_in: String,
+        token_out: String,
+        max_price_age: u64,
+    },
+
+    #[error("InvalidSlippageBps")]
+    InvalidSlippageBps {},
+
+    #[error("ZeroReferencePrice")]
+    ZeroReferencePrice {},
+
+    #[error("Reference quote for the swap at index {index} is zero")]
+    ZeroReferenceQuote { index: usize },
+
+    #[error("Callbacker {callbacker} at index {index} is not allowed")]
+    CallbackerNotAllowed { index: usize, callbacker: String },
+
+    #[error("Callbacker at index {index} is {actual}, registered {expected}")]
+    CallbackerMismatch {
+        index: usize,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Callback args at index {index} differ from the registered ones")]
+    CallbackArgsMismatch { index: usize },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
@@ -1,5 +1,8 @@
+pub mod abi;
+pub mod address;
 pub mod contract;
 mod error;
+mod migrations;
 pub mod msg;
 pub mod state;
 
@@ -0,0 +1,229 @@
+use cosmwasm_std::{Addr, Attribute, Order, StdResult, Storage, Timestamp, Uint256};
+use cw_storage_plus::{Item, Map};
+use ethabi::Address;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::error::ContractError;
+use crate::msg::Metadata;
+use crate::state::{
+    BackoffPolicy, Cooldown, State, CREATE_NEXT_BOT_COOLDOWNS, DEFAULT_MAX_PRICE_AGE,
+    REPAY_COOLDOWNS, STATE,
+};
+
+/// A migration returns attributes added to the `migrate` response.
+type Migration = fn(&mut dyn Storage) -> Result<Vec<Attribute>, ContractError>;
+
+/// State migrations in ascending version order. A migration runs when the
+/// stored contract version is below its version and the new code version is
+/// at or above it.
+const MIGRATIONS: &[(&str, Migration)] = &[("0.2.0", migrate_v0_2_0)];
+
+/// Runs every migration between `from` and `to`, returning the versions
+/// applied and the attributes they reported.
+pub fn run(
+    storage: &mut dyn Storage,
+    from: &Version,
+    to: &Version,
+) -> Result<(Vec<String>, Vec<Attribute>), ContractError> {
+    let mut applied: Vec<String> = vec![];
+    let mut attributes: Vec<Attribute> = vec![];
+    for (version, migration) in MIGRATIONS {
+        let version = Version::parse(version)?;
+        if from < &version && &version <= to {
+            attributes.extend(migration(storage)?);
+            applied.push(version.to_string());
+        }
+    }
+    Ok((applied, attributes))
+}
+
+/// `State` as stored by 0.1.x.
+#[derive(Serialize, Deserialize)]
+struct StateV0_1 {
+    retry_delay: u64,
+    job_id: String,
+    owner: Addr,
+    metadata: Metadata,
+}
+
+const STATE_V0_1: Item<StateV0_1> = Item::new("state");
+/// Cooldowns as stored by 0.1.x, keyed by `(bot, "repay")` for `RepayBot` and
+/// by `(bot_id, remaining_count)` for `CreateNextBot`.
+const WITHDRAW_TIMESTAMP: Map<(String, String), Timestamp> = Map::new("withdraw_timestamp");
+
+fn migrate_v0_2_0(storage: &mut dyn Storage) -> Result<Vec<Attribute>, ContractError> {
+    let state = STATE_V0_1.load(storage)?;
+    let retry_delay = state.retry_delay;
+    STATE.save(
+        storage,
+        &State {
+            retry_delay: state.retry_delay,
+            job_id: state.job_id,
+            owner: Some(state.owner),
+            metadata: state.metadata,
+            enforce_checksum: false,
+            backoff: BackoffPolicy::Fixed,
+            max_attempts: None,
+            max_price_age: DEFAULT_MAX_PRICE_AGE,
+        },
+    )?;
+    let dropped = move_cooldowns(storage, retry_delay)?;
+    Ok(vec![Attribute::new(
+        "dropped_cooldowns",
+        dropped.to_string(),
+    )])
+}
+
+/// Moves cooldowns out of the stringly keyed `withdraw_timestamp` map into
+/// the typed `RepayBot` and `CreateNextBot` maps. Differently spelled keys of
+/// one bot address are merged, keeping the latest timestamp. Cooldowns expire
+/// `retry_delay` after their timestamp. Keys that do not parse are removed and
+/// counted.
+fn move_cooldowns(storage: &mut dyn Storage, retry_delay: u64) -> Result<usize, ContractError> {
+    let entries = WITHDRAW_TIMESTAMP
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<((String, String), Timestamp)>>>()?;
+    let mut dropped = 0;
+    for ((first, second), timestamp) in entries {
+        WITHDRAW_TIMESTAMP.remove(storage, (first.clone(), second.clone()));
+        if second == "repay" {
+            let Ok(address) = Address::from_str(first.as_str()) else {
+                dropped += 1;
+                continue;
+            };
+            let latest = match REPAY_COOLDOWNS.may_load(storage, address.0)? {
+                Some(existing) if existing.submitted_at > timestamp => existing.submitted_at,
+                _ => timestamp,
+            };
+            REPAY_COOLDOWNS.save(storage, address.0, &Cooldown::new(latest, retry_delay))?;
+        } else {
+            let (Ok(bot_id), Ok(remaining_count)) = (
+                Uint256::from_str(first.as_str()),
+                Uint256::from_str(second.as_str()),
+            ) else {
+                dropped += 1;
+                continue;
+            };
+            CREATE_NEXT_BOT_COOLDOWNS.save(
+                storage,
+                (&bot_id.to_be_bytes(), &remaining_count.to_be_bytes()),
+                &Cooldown::new(timestamp, retry_delay),
+            )?;
+        }
+    }
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::migrate;
+    use crate::msg::MigrateMsg;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cw2::{get_contract_version, set_contract_version};
+
+    const CONTRACT_NAME: &str = "crates.io:curve-healthy-bot-cw";
+    const BOT: &str = "0x00000000000000000000000000000000000000ab";
+
+    /// The `state` item exactly as serialized by 0.1.0.
+    fn seed_v0_1(storage: &mut dyn Storage) {
+        set_contract_version(storage, CONTRACT_NAME, "0.1.0").unwrap();
+        storage.set(
+            b"state",
+            br#"{"retry_delay":60,"job_id":"job","owner":"owner","metadata":{"creator":"creator","signers":["signer"]}}"#,
+        );
+    }
+
+    fn save_withdraw_timestamp(storage: &mut dyn Storage, first: &str, second: &str, seconds: u64) {
+        WITHDRAW_TIMESTAMP
+            .save(
+                storage,
+                (first.to_string(), second.to_string()),
+                &Timestamp::from_seconds(seconds),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn migrates_v0_1_0_snapshot() {
+        let mut deps = mock_dependencies();
+        seed_v0_1(deps.as_mut().storage);
+        save_withdraw_timestamp(deps.as_mut().storage, BOT, "repay", 100);
+        save_withdraw_timestamp(deps.as_mut().storage, "7", "3", 200);
+        save_withdraw_timestamp(deps.as_mut().storage, "not-a-bot", "repay", 300);
+        save_withdraw_timestamp(deps.as_mut().storage, "7", "many", 400);
+
+        let response = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(response
+            .attributes
+            .contains(&Attribute::new("dropped_cooldowns", "2")));
+
+        let state = STATE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(state.retry_delay, 60);
+        assert_eq!(state.job_id, "job");
+        assert_eq!(state.owner, Some(Addr::unchecked("owner")));
+        assert_eq!(state.metadata.signers, vec!["signer".to_string()]);
+        assert!(!state.enforce_checksum);
+        assert_eq!(state.backoff, BackoffPolicy::Fixed);
+        assert_eq!(state.max_attempts, None);
+        assert_eq!(state.max_price_age, DEFAULT_MAX_PRICE_AGE);
+
+        let bot = Address::from_str(BOT).unwrap();
+        assert_eq!(
+            REPAY_COOLDOWNS.load(deps.as_ref().storage, bot.0).unwrap(),
+            Cooldown::new(Timestamp::from_seconds(100), 60)
+        );
+        let (bot_id, remaining_count) = (Uint256::from(7u8), Uint256::from(3u8));
+        assert_eq!(
+            CREATE_NEXT_BOT_COOLDOWNS
+                .load(
+                    deps.as_ref().storage,
+                    (&bot_id.to_be_bytes(), &remaining_count.to_be_bytes()),
+                )
+                .unwrap(),
+            Cooldown::new(Timestamp::from_seconds(200), 60)
+        );
+        assert!(WITHDRAW_TIMESTAMP.is_empty(deps.as_ref().storage));
+        assert_eq!(
+            get_contract_version(deps.as_ref().storage).unwrap().version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    #[test]
+    fn merges_differently_spelled_bot_keys() {
+        let mut deps = mock_dependencies();
+        seed_v0_1(deps.as_mut().storage);
+        let lowercase = BOT;
+        let mixed_case = BOT.replace("ab", "AB");
+        let unprefixed = BOT.trim_start_matches("0x");
+        save_withdraw_timestamp(deps.as_mut().storage, lowercase, "repay", 100);
+        save_withdraw_timestamp(deps.as_mut().storage, &mixed_case, "repay", 300);
+        save_withdraw_timestamp(deps.as_mut().storage, unprefixed, "repay", 200);
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let cooldowns = REPAY_COOLDOWNS
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        let bot = Address::from_str(BOT).unwrap();
+        assert_eq!(
+            cooldowns,
+            vec![(bot.0, Cooldown::new(Timestamp::from_seconds(300), 60))]
+        );
+        assert!(WITHDRAW_TIMESTAMP.is_empty(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn rejects_downgrade() {
+        let mut deps = mock_dependencies();
+        seed_v0_1(deps.as_mut().storage);
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::CannotMigrateVersion { .. }));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::state::{BackoffPolicy, Bot, BotStatus, ExecutionRecord, ReferencePrice, State};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, CustomMsg, Uint256};
+use cosmwasm_std::{Addr, Binary, CustomMsg, Decimal256, HexBinary, Timestamp, Uint256};
+use cw2::ContractVersion;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -7,8 +9,18 @@ pub struct InstantiateMsg {
     pub job_id: String,
     pub creator: String,
     pub signers: Vec<String>,
+    pub enforce_checksum: Option<bool>,
+    pub backoff: Option<BackoffPolicy>,
+    pub max_attempts: Option<u32>,
+    /// Defaults to `DEFAULT_MAX_PRICE_AGE`.
+    pub max_price_age: Option<u64>,
+    /// Vyper ABI JSON of the bot factory. Defaults to the bundled ABI.
+    pub abi: Option<String>,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub struct BotInfo {
     pub bot: String,
@@ -36,6 +48,33 @@ pub enum ExecuteMsg {
     },
     RepayBot {
         bot_info: Vec<BotInfo>,
+        /// Pad short `route`/`pools` with the zero address and short
+        /// `swap_params` with zeros up to the fixed ABI sizes.
+        pad_swap_info: Option<bool>,
+    },
+    /// `RepayBot` with the callbacker and callback args of each bot taken
+    /// from the registry.
+    RepayBotByAddress {
+        bots: Vec<(String, SwapInfo)>,
+        pad_swap_info: Option<bool>,
+    },
+    /// Register a bot deployed by the Vyper factory. Only registered, active
+    /// bots are accepted by `RepayBot`.
+    RegisterBot {
+        bot: String,
+        owner: String,
+        callbacker: String,
+        callback_args: Vec<Uint256>,
+        collateral: String,
+        market: String,
+    },
+    /// Mark a bot as deregistered. Its record is kept for queries.
+    DeregisterBot {
+        bot: String,
+    },
+    /// Remove up to `limit` cooldowns whose retry delay has passed.
+    PruneCooldowns {
+        limit: Option<u32>,
     },
     SetPaloma {},
     UpdateCompass {
@@ -56,6 +95,173 @@ pub enum ExecuteMsg {
     UpdateServiceFee {
         new_service_fee: Uint256,
     },
+    /// Propose a new owner who must accept within `expires_in` seconds, if set.
+    ProposeOwner {
+        new_owner: String,
+        expires_in: Option<u64>,
+    },
+    AcceptOwnership {},
+    CancelOwnershipTransfer {},
+    RenounceOwnership {},
+    /// Allow `operator` to run `RepayBot` and `CreateNextBot`.
+    AddOperator {
+        operator: String,
+    },
+    RemoveOperator {
+        operator: String,
+    },
+    /// Update the Paloma job and metadata. Omitted fields are left unchanged.
+    UpdateConfig {
+        job_id: Option<String>,
+        retry_delay: Option<u64>,
+        creator: Option<String>,
+        signers: Option<Vec<String>>,
+        enforce_checksum: Option<bool>,
+        max_price_age: Option<u64>,
+    },
+    /// Allow `callbacker` in `RepayBot` and `CreateNextBot`.
+    AddCallbacker {
+        callbacker: String,
+    },
+    RemoveCallbacker {
+        callbacker: String,
+    },
+    /// Approve Curve pools or tokens for `SwapInfo` routes.
+    AddSwapAddresses {
+        kind: SwapAddressKind,
+        addresses: Vec<String>,
+    },
+    RemoveSwapAddresses {
+        kind: SwapAddressKind,
+        addresses: Vec<String>,
+    },
+    /// Set the max slippage of `RepayBot` swaps from `token_in` to
+    /// `token_out`. `None` removes the bound.
+    SetMaxSlippage {
+        token_in: String,
+        token_out: String,
+        max_slippage_bps: Option<u16>,
+    },
+    /// Report the price of `token_in` in `token_out` units.
+    SetReferencePrice {
+        token_in: String,
+        token_out: String,
+        price: Decimal256,
+    },
+    /// Allow `oracle` to run `SetReferencePrice`.
+    AddOracle {
+        oracle: String,
+    },
+    RemoveOracle {
+        oracle: String,
+    },
+    /// Allow `reporter` to confirm executions with `ConfirmExecution`.
+    AddReporter {
+        reporter: String,
+    },
+    RemoveReporter {
+        reporter: String,
+    },
+    /// Report the EVM outcome of the submission of `keys` awaiting
+    /// confirmation. A failure keeps the cooldown and its backoff.
+    ConfirmExecution {
+        action: Action,
+        keys: Vec<CooldownKey>,
+        success: bool,
+        evm_tx_hash: String,
+    },
+    /// Call an allowlisted function of the stored ABI with `args` converted
+    /// to its input types.
+    RawCall {
+        function: String,
+        args: Vec<AbiValue>,
+    },
+    /// Allow `function` in `RawCall`. It must exist in the stored ABI.
+    AddRawCallFunction {
+        function: String,
+    },
+    RemoveRawCallFunction {
+        function: String,
+    },
+    /// Replace the Vyper ABI JSON. Every function the contract calls must
+    /// be declared with the expected inputs.
+    UpdateAbi {
+        abi: String,
+    },
+    /// Set the retry delay of `action`. `None` falls back to `retry_delay`.
+    UpdateRetryDelay {
+        action: Action,
+        retry_delay: Option<u64>,
+    },
+    /// Set the `RepayBot` backoff policy and the attempts without a confirmed
+    /// success after which a bot is quarantined. `None` never quarantines.
+    UpdateRetryPolicy {
+        backoff: BackoffPolicy,
+        max_attempts: Option<u32>,
+    },
+    /// Clear the attempt counter of a bot and release it from quarantine.
+    ResetBotAttempts {
+        bot: String,
+    },
+}
+
+/// A `RawCall` argument. Integers are decimal strings and bytes are hex.
+#[cw_serde]
+pub enum AbiValue {
+    Address(String),
+    Uint(Uint256),
+    Bool(bool),
+    Bytes(HexBinary),
+    /// A dynamic or fixed size array.
+    Array(Vec<AbiValue>),
+    Tuple(Vec<AbiValue>),
+}
+
+/// Actions submitted to Paloma that are rate limited by a cooldown.
+#[cw_serde]
+pub enum Action {
+    Repay,
+    CreateNextBot,
+}
+
+pub const ACTIONS: [Action; 2] = [Action::Repay, Action::CreateNextBot];
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Repay => "repay",
+            Action::CreateNextBot => "create_next_bot",
+        }
+    }
+}
+
+/// Route entries alternate tokens and pools: `route[2i]` is a token,
+/// `route[2i + 1]` a pool. Every entry of `pools` is a pool.
+#[cw_serde]
+pub enum SwapAddressKind {
+    Pool,
+    Token,
+}
+
+impl SwapAddressKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwapAddressKind::Pool => "pool",
+            SwapAddressKind::Token => "token",
+        }
+    }
+}
+
+#[cw_serde]
+pub enum Role {
+    /// The contract owner. Runs admin messages and everything operators can.
+    Admin,
+    /// Scheduler keys allowed to run `RepayBot` and `CreateNextBot`.
+    Operator,
+    /// Relayers allowed to run `ConfirmExecution`.
+    Reporter,
+    /// Price feeders allowed to run `SetReferencePrice`.
+    Oracle,
 }
 
 #[cw_serde]
@@ -82,6 +288,91 @@ pub enum QueryMsg {
     // GetCount returns the current count as a json-encoded number
     #[returns(GetJobIdResponse)]
     GetJobId {},
+    #[returns(GetConfigResponse)]
+    GetConfig {},
+    #[returns(GetOwnershipResponse)]
+    GetOwnership {},
+    /// Cooldown of a bot submitted through `RepayBot`.
+    #[returns(CooldownResponse)]
+    GetCooldown { bot: String },
+    /// Cooldown of a `CreateNextBot` submission.
+    #[returns(CooldownResponse)]
+    GetCreateNextBotCooldown {
+        bot_id: Uint256,
+        remaining_count: Uint256,
+    },
+    #[returns(ListCooldownsResponse)]
+    ListCooldowns {
+        start_after: Option<CooldownKey>,
+        limit: Option<u32>,
+    },
+    #[returns(ListQuarantinedBotsResponse)]
+    ListQuarantinedBots {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Reported `RepayBot` executions of a bot, newest first.
+    #[returns(ExecutionHistoryResponse)]
+    GetRepayHistory {
+        bot: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Reported `CreateNextBot` executions of a bot, newest first.
+    #[returns(ExecutionHistoryResponse)]
+    GetCreateNextBotHistory {
+        bot_id: Uint256,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(BotResponse)]
+    GetBot { bot: String },
+    /// Registered bots ordered by address, optionally filtered.
+    #[returns(ListBotsResponse)]
+    ListBots {
+        filter: Option<BotFilter>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ListCallbackersResponse)]
+    ListCallbackers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ListSwapAddressesResponse)]
+    ListSwapAddresses {
+        kind: SwapAddressKind,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(SlippageResponse)]
+    GetSlippage { token_in: String, token_out: String },
+    #[returns(ListRawCallFunctionsResponse)]
+    ListRawCallFunctions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The `RepayBot` payload that would be sent at the current block,
+    /// without submitting it or starting any cooldown.
+    #[returns(SimulateRepayBotResponse)]
+    SimulateRepayBot {
+        bot_info: Vec<BotInfo>,
+        pad_swap_info: Option<bool>,
+    },
+    /// The `CreateNextBot` payload that would be sent at the current block.
+    #[returns(SimulateCreateNextBotResponse)]
+    SimulateCreateNextBot {
+        bot_id: Uint256,
+        callbacker: String,
+        callback_args: Vec<Uint256>,
+        remaining_count: Uint256,
+    },
+    #[returns(GetRoleHoldersResponse)]
+    GetRoleHolders {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 // We define a custom struct for each query response
@@ -90,4 +381,180 @@ pub struct GetJobIdResponse {
     pub job_id: String,
 }
 
+#[cw_serde]
+pub struct GetConfigResponse {
+    pub state: State,
+    /// Retry delays overriding `state.retry_delay` per action.
+    pub retry_delays: Vec<(Action, u64)>,
+    pub version: ContractVersion,
+}
+
+#[cw_serde]
+pub struct GetOwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct QuarantinedBot {
+    pub bot: String,
+    pub attempts: u32,
+    pub quarantined_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct ListQuarantinedBotsResponse {
+    pub bots: Vec<QuarantinedBot>,
+}
+
+#[cw_serde]
+pub enum BotFilter {
+    Owner { owner: String },
+    Callbacker { callbacker: String },
+    Status { status: BotStatus },
+}
+
+#[cw_serde]
+pub struct BotResponse {
+    pub bot: String,
+    pub info: Bot,
+}
+
+#[cw_serde]
+pub struct ListBotsResponse {
+    pub bots: Vec<BotResponse>,
+}
+
+#[cw_serde]
+pub struct ListCallbackersResponse {
+    pub callbackers: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ListSwapAddressesResponse {
+    pub kind: SwapAddressKind,
+    pub addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SlippageResponse {
+    pub max_slippage_bps: Option<u16>,
+    pub reference_price: Option<ReferencePrice>,
+}
+
+#[cw_serde]
+pub struct ListRawCallFunctionsResponse {
+    pub functions: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SimulateRepayBotResponse {
+    /// 4-byte selector of `repay_bot`.
+    pub selector: HexBinary,
+    /// `None` when every bot is skipped or the call is invalid.
+    pub payload: Option<HexBinary>,
+    /// Bots that would be included in the Paloma message.
+    pub submitted: Vec<String>,
+    pub skipped: Vec<SkippedBot>,
+    /// The error `RepayBot` would fail with.
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct SimulateCreateNextBotResponse {
+    /// 4-byte selector of `create_next_bot`.
+    pub selector: HexBinary,
+    /// `None` while in cooldown or when the call is invalid.
+    pub payload: Option<HexBinary>,
+    pub cooldown: Option<CooldownResponse>,
+    /// The error `CreateNextBot` would fail with.
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct ExecutionEntry {
+    pub id: u64,
+    pub record: ExecutionRecord,
+}
+
+#[cw_serde]
+pub struct ExecutionHistoryResponse {
+    pub records: Vec<ExecutionEntry>,
+}
+
+#[cw_serde]
+pub struct GetRoleHoldersResponse {
+    pub role: Role,
+    pub holders: Vec<Addr>,
+}
+
+#[cw_serde]
+#[derive(Eq)]
+pub enum CooldownKey {
+    Repay {
+        bot: String,
+    },
+    CreateNextBot {
+        bot_id: Uint256,
+        remaining_count: Uint256,
+    },
+}
+
+#[cw_serde]
+pub struct CooldownResponse {
+    /// Last time the key was submitted to Paloma.
+    pub last_submitted: Option<Timestamp>,
+    /// The key is eligible again once the block time is past this time.
+    /// `None` for a `CreateNextBot` key confirmed as executed.
+    pub next_eligible: Option<Timestamp>,
+    /// Whether the key would be submitted at the current block.
+    pub eligible: bool,
+    /// Outcome of the last submission reported through `ConfirmExecution`.
+    pub confirmed: Option<bool>,
+}
+
+#[cw_serde]
+pub struct CooldownEntry {
+    pub key: CooldownKey,
+    pub cooldown: CooldownResponse,
+}
+
+#[cw_serde]
+pub struct ListCooldownsResponse {
+    pub cooldowns: Vec<CooldownEntry>,
+}
+
+/// Returned as the `data` of a `RepayBot` response.
+#[cw_serde]
+pub struct RepayBotResult {
+    /// Bots included in the Paloma message.
+    pub submitted: Vec<String>,
+    pub skipped: Vec<SkippedBot>,
+}
+
+#[cw_serde]
+pub struct SkippedBot {
+    pub bot: String,
+    pub reason: SkipReason,
+    pub next_eligible: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub enum SkipReason {
+    /// Submitted within the retry delay.
+    Cooldown,
+    /// Reached `max_attempts` without a confirmed success.
+    Quarantined,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::Cooldown => write!(f, "cooldown"),
+            SkipReason::Quarantined => write!(f, "quarantined"),
+        }
+    }
+}
+
 impl CustomMsg for PalomaMsg {}
@@ -1,17 +1,303 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::msg::Metadata;
-use cosmwasm_std::{Addr, Timestamp};
-use cw_storage_plus::{Item, Map};
+use crate::msg::{CooldownKey, Metadata};
+use cosmwasm_std::{Addr, Decimal256, Empty, Timestamp, Uint256};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct State {
     pub retry_delay: u64,
     pub job_id: String,
-    pub owner: Addr,
+    pub owner: Option<Addr>,
     pub metadata: Metadata,
+    /// Require EVM addresses in messages to be EIP-55 checksummed.
+    pub enforce_checksum: bool,
+    /// How the `RepayBot` retry delay grows with each attempt on a bot.
+    pub backoff: BackoffPolicy,
+    /// Submissions of a bot without a confirmed success after which it is
+    /// quarantined and no longer submitted.
+    pub max_attempts: Option<u32>,
+    /// Seconds after which a reference price no longer bounds slippage and
+    /// the swap is rejected.
+    pub max_price_age: u64,
+}
+
+/// `max_price_age` when none is given: one day.
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 86_400;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BackoffPolicy {
+    /// `retry_delay` after every attempt.
+    Fixed,
+    /// `retry_delay * attempts`.
+    Linear,
+    /// `retry_delay * 2^(attempts - 1)`, capped at `max_delay` seconds.
+    Exponential { max_delay: u64 },
+}
+
+impl BackoffPolicy {
+    /// The delay in seconds after `attempts` submissions. Never shorter than
+    /// `retry_delay`.
+    pub fn delay(&self, retry_delay: u64, attempts: u32) -> u64 {
+        match self {
+            BackoffPolicy::Fixed => retry_delay,
+            BackoffPolicy::Linear => retry_delay.saturating_mul(u64::from(attempts.max(1))),
+            BackoffPolicy::Exponential { max_delay } => {
+                let exponent = attempts.saturating_sub(1).min(63);
+                retry_delay
+                    .saturating_mul(1u64 << exponent)
+                    .min(*max_delay)
+                    .max(retry_delay)
+            }
+        }
+    }
+}
+
+/// Outcome of a Paloma submission as reported through `ConfirmExecution`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ExecutionRecord {
+    pub key: CooldownKey,
+    pub success: bool,
+    pub evm_tx_hash: String,
+    pub reporter: Addr,
+    pub reported_at: Timestamp,
+}
+
+/// A bot deployed by the Vyper factory. Addresses are lowercase `0x` hex.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Bot {
+    pub owner: String,
+    pub callbacker: String,
+    /// Callback arguments fixed at bot creation.
+    pub callback_args: Vec<Uint256>,
+    pub collateral: String,
+    pub market: String,
+    pub created_at: Timestamp,
+    pub status: BotStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BotStatus {
+    Active,
+    Deregistered,
 }
 
-pub const WITHDRAW_TIMESTAMP: Map<(String, String), Timestamp> = Map::new("withdraw_timestamp");
+impl BotStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BotStatus::Active => "active",
+            BotStatus::Deregistered => "deregistered",
+        }
+    }
+}
+
+/// Output token units per input token unit, as last reported by an oracle.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ReferencePrice {
+    pub price: Decimal256,
+    pub updated_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Last submission of a cooldown key to Paloma.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Cooldown {
+    pub submitted_at: Timestamp,
+    /// `submitted_at` plus the retry delay in force at submission, grown by
+    /// the backoff policy for `RepayBot`. The key is eligible again once the
+    /// block time is past it.
+    pub expires_at: Timestamp,
+    /// Outcome reported through `ConfirmExecution`, `None` while the
+    /// submission awaits confirmation.
+    pub confirmed: Option<bool>,
+}
+
+impl Cooldown {
+    pub fn new(submitted_at: Timestamp, retry_delay: u64) -> Self {
+        Cooldown {
+            submitted_at,
+            expires_at: submitted_at.plus_seconds(retry_delay),
+            confirmed: None,
+        }
+    }
+}
+
+/// Indexes cooldowns by expiry so expired entries can be pruned without
+/// visiting the ones still pending.
+pub struct CooldownIndexes<'a, PK> {
+    pub expires: MultiIndex<'a, u64, Cooldown, PK>,
+}
+
+impl<PK> IndexList<Cooldown> for CooldownIndexes<'_, PK> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Cooldown>> + '_> {
+        let v: Vec<&dyn Index<Cooldown>> = vec![&self.expires];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Last `RepayBot` submission per bot address.
+pub const REPAY_COOLDOWNS: IndexedMap<[u8; 20], Cooldown, CooldownIndexes<[u8; 20]>> =
+    IndexedMap::new(
+        "repay_cooldowns",
+        CooldownIndexes {
+            expires: MultiIndex::new(
+                |_pk, cooldown| cooldown.expires_at.nanos(),
+                "repay_cooldowns",
+                "repay_cooldowns__expires",
+            ),
+        },
+    );
+type CreateNextBotCooldowns<'a> =
+    IndexedMap<(&'a [u8], &'a [u8]), Cooldown, CooldownIndexes<'a, (Vec<u8>, Vec<u8>)>>;
+
+/// Last `CreateNextBot` submission per big-endian `(bot_id, remaining_count)`.
+pub const CREATE_NEXT_BOT_COOLDOWNS: CreateNextBotCooldowns = IndexedMap::new(
+    "create_next_bot_cooldowns",
+    CooldownIndexes {
+        expires: MultiIndex::new(
+            |_pk, cooldown| cooldown.expires_at.nanos(),
+            "create_next_bot_cooldowns",
+            "create_next_bot_cooldowns__expires",
+        ),
+    },
+);
+/// `CreateNextBot` keys confirmed as executed, with their last submission.
+/// They are never submitted again.
+pub const EXECUTED_NEXT_BOTS: Map<(&[u8], &[u8]), Cooldown> = Map::new("executed_next_bots");
+pub struct BotIndexes<'a> {
+    pub owner: MultiIndex<'a, String, Bot, [u8; 20]>,
+    pub callbacker: MultiIndex<'a, String, Bot, [u8; 20]>,
+    pub status: MultiIndex<'a, String, Bot, [u8; 20]>,
+}
+
+impl IndexList<Bot> for BotIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bot>> + '_> {
+        let v: Vec<&dyn Index<Bot>> = vec![&self.owner, &self.callbacker, &self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Registered bots per bot address.
+pub const BOTS: IndexedMap<[u8; 20], Bot, BotIndexes> = IndexedMap::new(
+    "bots",
+    BotIndexes {
+        owner: MultiIndex::new(|_pk, bot| bot.owner.clone(), "bots", "bots__owner"),
+        callbacker: MultiIndex::new(
+            |_pk, bot| bot.callbacker.clone(),
+            "bots",
+            "bots__callbacker",
+        ),
+        status: MultiIndex::new(
+            |_pk, bot| bot.status.as_str().to_string(),
+            "bots",
+            "bots__status",
+        ),
+    },
+);
 pub const STATE: Item<State> = Item::new("state");
+/// Vyper ABI JSON set with `UpdateAbi`.
+pub const ABI: Item<String> = Item::new("abi");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const OPERATORS: Map<&Addr, Empty> = Map::new("operators");
+pub const REPORTERS: Map<&Addr, Empty> = Map::new("reporters");
+/// Callbacker addresses `RepayBot` and `CreateNextBot` may forward.
+pub const CALLBACKER_ALLOWLIST: Map<[u8; 20], Empty> = Map::new("callbacker_allowlist");
+/// Reported executions per `(action, bot, id)`. The bot is the address bytes
+/// for `repay` and the big-endian `bot_id` for `create_next_bot`.
+pub const EXECUTION_HISTORY: Map<(&str, &[u8], u64), ExecutionRecord> =
+    Map::new("execution_history");
+/// Last id assigned to an `ExecutionRecord`.
+pub const EXECUTION_SEQ: Item<u64> = Item::new("execution_seq");
+/// Vyper functions `RawCall` may call.
+pub const RAW_CALL_ALLOWLIST: Map<&str, Empty> = Map::new("raw_call_allowlist");
+/// Curve pools `SwapInfo` may route through.
+pub const APPROVED_POOLS: Map<[u8; 20], Empty> = Map::new("approved_pools");
+/// Tokens `SwapInfo` may route through.
+pub const APPROVED_TOKENS: Map<[u8; 20], Empty> = Map::new("approved_tokens");
+pub const ORACLES: Map<&Addr, Empty> = Map::new("oracles");
+/// Max slippage in basis points per `(token_in, token_out)` address pair.
+pub const MAX_SLIPPAGE: Map<(&[u8], &[u8]), u16> = Map::new("max_slippage");
+/// Reference price per `(token_in, token_out)` address pair.
+pub const REFERENCE_PRICES: Map<(&[u8], &[u8]), ReferencePrice> = Map::new("reference_prices");
+/// Retry delays per `Action`, overriding `State.retry_delay`.
+pub const RETRY_DELAYS: Map<&str, u64> = Map::new("retry_delays");
+/// `RepayBot` submissions per bot address since the last reset.
+pub const BOT_ATTEMPTS: Map<[u8; 20], u32> = Map::new("bot_attempts");
+/// Bots that reached `max_attempts` without a confirmed success, with the time
+/// they were quarantined.
+pub const QUARANTINED_BOTS: Map<[u8; 20], Timestamp> = Map::new("quarantined_bots");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{Order, StdResult};
+    use std::str::FromStr;
+
+    #[test]
+    fn cooldown_namespaces_do_not_collide() {
+        let mut storage = MockStorage::new();
+        // a bot and a `(bot_id, remaining_count)` read from the same string
+        let source = "1";
+        let bot = ethabi::Address::from_str(&format!("{source:0>40}"))
+            .unwrap()
+            .0;
+        let one = Uint256::from_str(source).unwrap().to_be_bytes();
+        REPAY_COOLDOWNS
+            .save(
+                &mut storage,
+                bot,
+                &Cooldown::new(Timestamp::from_seconds(100), 60),
+            )
+            .unwrap();
+        CREATE_NEXT_BOT_COOLDOWNS
+            .save(
+                &mut storage,
+                (&one, &one),
+                &Cooldown::new(Timestamp::from_seconds(200), 60),
+            )
+            .unwrap();
+
+        assert_eq!(
+            REPAY_COOLDOWNS
+                .range(&storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec![(bot, Cooldown::new(Timestamp::from_seconds(100), 60))]
+        );
+        assert_eq!(
+            CREATE_NEXT_BOT_COOLDOWNS
+                .range(&storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec![(
+                (one.to_vec(), one.to_vec()),
+                Cooldown::new(Timestamp::from_seconds(200), 60)
+            )]
+        );
+        assert_eq!(
+            REPAY_COOLDOWNS
+                .idx
+                .expires
+                .range_raw(&storage, None, None, Order::Ascending)
+                .count(),
+            1
+        );
+        assert_eq!(
+            CREATE_NEXT_BOT_COOLDOWNS
+                .idx
+                .expires
+                .range_raw(&storage, None, None, Order::Ascending)
+                .count(),
+            1
+        );
+    }
+}